@@ -0,0 +1,126 @@
+//! Compares the ways of loading the dictionary at startup: parsing
+//! `dictionary.json` into a `HashSet` as the game used to, packing the same
+//! JSON, and using the compact `dictionary.bin` in place as the game does now.
+//!
+//! Run from the source tree with `cargo run --release --example dictionary_bench`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use wordle::word::dictionary::{self, Dictionary};
+
+const JSON_PATH: &str = "assets/word/dictionary.json";
+const BIN_PATH: &str = "assets/word/dictionary.bin";
+const RUNS: u32 = 10;
+
+/// Counts live heap bytes and the most live at once, for `measure`.
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(live, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+struct Measurement {
+    time: Duration,
+    /// Heap still held by the loaded dictionary.
+    retained: usize,
+    /// Most heap in use at once while loading.
+    peak: usize,
+}
+
+/// Times `load` over `RUNS` runs and measures the heap of one.
+fn measure<T>(load: impl Fn() -> T) -> Measurement {
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        black_box(load());
+    }
+    let time = start.elapsed() / RUNS;
+
+    let before = LIVE.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let loaded = load();
+    let retained = LIVE.load(Ordering::Relaxed) - before;
+    let peak = PEAK.load(Ordering::Relaxed) - before;
+    drop(black_box(loaded));
+    Measurement {
+        time,
+        retained,
+        peak,
+    }
+}
+
+fn report(name: &str, m: &Measurement) {
+    println!(
+        "{:<34} {:>9.3} ms {:>9.1} MiB {:>9.1} MiB",
+        name,
+        m.time.as_secs_f64() * 1000.0,
+        m.retained as f64 / (1024.0 * 1024.0),
+        m.peak as f64 / (1024.0 * 1024.0),
+    );
+}
+
+fn main() {
+    let json = std::fs::read_to_string(JSON_PATH).unwrap_or_else(|err| panic!("{}: {}", JSON_PATH, err));
+    // Leaked so the compact dictionary can borrow it for 'static, the way
+    // the game borrows the copy compiled into the binary.
+    let bin: &'static [u8] = std::fs::read(BIN_PATH)
+        .unwrap_or_else(|err| panic!("{}: {}", BIN_PATH, err))
+        .leak();
+    println!("{}: {} bytes, {}: {} bytes", JSON_PATH, json.len(), BIN_PATH, bin.len());
+    println!();
+
+    println!("{:<34} {:>12} {:>13} {:>13}", "Load", "time", "retained", "peak");
+    let old = measure(|| dictionary::parse_json(&json).unwrap());
+    report("JSON -> HashSet (before)", &old);
+    let packed = measure(|| Dictionary::from_json(&json).unwrap());
+    report("JSON -> Dictionary", &packed);
+    let compact = measure(|| Dictionary::from_bytes(Cow::Borrowed(bin)).unwrap());
+    report("dictionary.bin in place (now)", &compact);
+    println!();
+
+    // Every word, then as many misses, made by changing the last letter.
+    let set = dictionary::parse_json(&json).unwrap();
+    let dictionary = Dictionary::from_bytes(Cow::Borrowed(bin)).unwrap();
+    let mut probes: Vec<String> = set.iter().cloned().collect();
+    probes.extend(set.iter().map(|w| {
+        let mut miss = w.clone();
+        miss.pop();
+        miss.push('q');
+        miss
+    }));
+
+    println!("{:<34} {:>12}", "Lookup", "per word");
+    let start = Instant::now();
+    let found = probes.iter().filter(|w| set.contains(w.as_str())).count();
+    report_lookup("HashSet", start.elapsed(), probes.len(), found);
+    let start = Instant::now();
+    let found = probes.iter().filter(|w| dictionary.contains(w)).count();
+    report_lookup("Dictionary", start.elapsed(), probes.len(), found);
+}
+
+fn report_lookup(name: &str, elapsed: Duration, probes: usize, found: usize) {
+    println!(
+        "{:<34} {:>9.0} ns  ({} of {} found)",
+        name,
+        elapsed.as_nanos() as f64 / probes as f64,
+        found,
+        probes
+    );
+}
@@ -0,0 +1,765 @@
+use std::time::Duration;
+
+use crate::{
+    game::{
+        daily::Date,
+        judge::MAX_PATTERN_LEN,
+        multi::MAX_BOARDS,
+        solver,
+        strategy::{DEFAULT_MATRIX_MIB, STRATEGY_NAMES},
+    },
+    types::*,
+    word::query::{WordQuery, WordSource},
+};
+
+pub const USAGE: &str = "\
+Usage: wordle [COMMAND] [OPTIONS]
+
+Commands:
+  play             Play a random puzzle (default)
+  daily            Play today's puzzle, or a past one
+  stats            Show statistics of finished games
+  solve            Suggest guesses for a puzzle played elsewhere
+  bench            Play every answer with solver strategies and compare them
+  query            List words matching letter constraints
+  validate-words   Check the answer list against the dictionary
+  build-tiers      Regenerate the per-difficulty answer lists by word frequency
+  build-dictionary Convert the dictionary to the compact format built into the binary
+  packs            List the installed word packs and check their manifests
+  help             Show this message
+
+Options for play and daily:
+  --ui <plain|tui>                    User interface (default: tui)
+  --difficulty <easy|medium|hard>     Difficulty (default: easy, daily: medium)
+  --length <N>                        Answer length (default: random, daily: from the date)
+  --attempts <N>                      Number of guesses allowed
+  --answer-file <PATH>                Answer list to draw from
+  --seed <N>                          Replay the puzzle with this seed (play only)
+  --mode <MODE>                       classic, absurdle, fibble or mastermind (default: classic)
+  --lies <1|2>                        Lying tiles per row in fibble (default: 1)
+  --time <SECS|M:SS>                  Time limit for the game
+  --blitz <MINUTES>                   Solve puzzles in a row until the time runs out (play only)
+  --boards <N>                        Play N answers at once, or dordle, quordle, octordle (play only)
+  --pack <NAME>                       Play a word pack instead of the built-in words (play only)
+  --date <YYYY-MM-DD>                 Play the puzzle of this date (daily only)
+  --number <N>                        Play puzzle number N (daily only)
+
+Options for stats:
+  --length <N>                        Only show games of this length
+
+Options for solve:
+  --length <N>                        Answer length (default: 5)
+  --answer-file <PATH>                Answer list to draw candidates from
+
+Options for bench:
+  --length <N>                        Answer length (default: 5)
+  --strategy <NAME|all>               entropy, minimax, expected, random or all (default: all)
+  --attempts <N>                      Guesses allowed per game (default: 6)
+  --limit <N>                         Only play the first N answers
+  --seed <N>                          Seed for the random strategy
+  --answer-file <PATH>                Answer list to play through
+  --matrix-mib <N>                    Memory for precomputed patterns in MiB, 5 letters need 80 (default: 32)
+
+Options for query (positions are 1-based, letter options can repeat):
+  --length <N>                        Word length
+  --pattern <a..e.>                   Known letters, '.' for unknown; sets the length
+  --known <POS=L>                     Letter L is at position POS
+  --present <L[:POS,POS]>             Letter L appears, but not at the listed positions
+  --absent <LETTERS>                  None of these letters appear
+  --min <L=N>                         Letter L appears at least N times
+  --max <L=N>                         Letter L appears at most N times
+  --guess <WORD:FEEDBACK>             Everything a guess revealed, e.g. crane:gy..g
+  --source <answers|dictionary>       Which list to search (default: answers)
+  --limit <N>                         Print at most N words (default: 50)
+
+Options for validate-words:
+  --answer-file <PATH>                Answer list to check
+  --dictionary-file <PATH>            Dictionary to check against
+
+Options for build-tiers:
+  --frequency-file <PATH>             Words ranked most common first (default: the order of the answer file)
+  --answer-file <PATH>                Answer pool to split (default: assets/word/answer.json)
+  --dictionary-file <PATH>            Dictionary every answer must be in (default: assets/word/dictionary.json)
+  --check <true|false>                Only check the tier files are up to date
+  build-tiers reads and writes assets/word, so run it from the source tree.
+  answer.json lists every length's words most common first.
+
+Options for build-dictionary:
+  --dictionary-file <PATH>            Dictionary JSON to convert (default: assets/word/dictionary.json)
+  --output <PATH>                     Where to write it (default: assets/word/dictionary.bin)
+  --check <true|false>                Only check the output is up to date
+
+Word lists and config:
+  The word lists are built into the binary. A file with the same name
+  (dictionary.bin or dictionary.json, answer.json, answer-easy.json, ...)
+  replaces the built-in list when found in $WORDLE_WORD_DIR, $XDG_DATA_HOME/wordle/word or
+  wordle/word under $XDG_DATA_DIRS, searched in that order.
+  Colours and share style are read from $WORDLE_CONFIG, or
+  $XDG_CONFIG_HOME/wordle/config.toml (default ~/.config/wordle/config.toml).
+
+Word packs:
+  A pack is a NAME.toml manifest in $WORDLE_PACK_DIR, $XDG_DATA_HOME/wordle/packs
+  or wordle/packs under $XDG_DATA_DIRS; assets/packs in the source tree has
+  examples. When packs are installed and --pack isn't given, the tui asks
+  which one to play.
+";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PlayArgs {
+    pub ui: ConsoleUiType,
+    pub difficulty: GameDifficulty,
+    pub length: Option<usize>,
+    pub attempts: Option<u8>,
+    pub seed: Option<u64>,
+    pub answer_file: Option<String>,
+    pub mode: GameMode,
+    pub time_limit: Option<Duration>,
+    /// Length of a blitz run; set means play puzzles back to back.
+    pub blitz: Option<Duration>,
+    /// Number of answers played at once; more than one is a multi-board game.
+    pub boards: usize,
+    /// Id of the word pack to play instead of the built-in answers.
+    pub pack: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DailyPick {
+    Today,
+    Date(Date),
+    Number(u32),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Command {
+    Play(PlayArgs),
+    Daily(PlayArgs, DailyPick),
+    Stats { length: Option<usize> },
+    Solve {
+        length: usize,
+        answer_file: Option<String>,
+    },
+    Bench {
+        length: usize,
+        strategies: Vec<String>,
+        attempts: u8,
+        limit: Option<usize>,
+        seed: u64,
+        answer_file: Option<String>,
+        /// Memory the pattern matrix may take, in MiB.
+        matrix_mib: usize,
+    },
+    Query {
+        query: WordQuery,
+        source: WordSource,
+        limit: usize,
+    },
+    ValidateWords {
+        answer_file: Option<String>,
+        dictionary_file: Option<String>,
+    },
+    BuildTiers {
+        frequency_file: Option<String>,
+        answer_file: Option<String>,
+        dictionary_file: Option<String>,
+        check: bool,
+    },
+    BuildDictionary {
+        dictionary_file: Option<String>,
+        output: Option<String>,
+        check: bool,
+    },
+    Packs,
+    Help,
+}
+
+/// Walks `--flag value` and `--flag=value` pairs.
+struct Flags<'a> {
+    args: std::slice::Iter<'a, String>,
+}
+
+impl<'a> Flags<'a> {
+    fn next(&mut self) -> Result<Option<(String, String)>, String> {
+        let arg = match self.args.next() {
+            Some(arg) => arg,
+            None => return Ok(None),
+        };
+        if arg == "-h" || arg == "--help" {
+            return Ok(Some((String::from("--help"), String::new())));
+        }
+        if !arg.starts_with("--") {
+            return Err(format!("Unexpected argument '{}'.", arg));
+        }
+        if let Some((flag, value)) = arg.split_once('=') {
+            return Ok(Some((flag.to_string(), value.to_string())));
+        }
+        match self.args.next() {
+            Some(value) => Ok(Some((arg.clone(), value.clone()))),
+            None => Err(format!("Missing value for {}.", arg)),
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value '{}' for {}, expected a number.", value, flag))
+}
+
+fn parse_ui(value: &str) -> Result<ConsoleUiType, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "plain" => Ok(ConsoleUiType::Plain),
+        "tui" | "ratatui" => Ok(ConsoleUiType::Ratatui),
+        _ => Err(format!("Unknown ui '{}', expected 'plain' or 'tui'.", value)),
+    }
+}
+
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let (command, rest) = match args.first().map(String::as_str) {
+        None => ("play", &args[0..0]),
+        Some(first) if first.starts_with("--") => ("play", args),
+        Some(first) => (first, &args[1..]),
+    };
+    let mut flags = Flags { args: rest.iter() };
+    match command {
+        "play" | "daily" => {
+            let daily = command == "daily";
+            let mut play = PlayArgs {
+                ui: ConsoleUiType::Ratatui,
+                difficulty: if daily { GameDifficulty::Medium } else { GameDifficulty::Easy },
+                length: None,
+                attempts: None,
+                seed: None,
+                answer_file: None,
+                mode: GameMode::Classic,
+                time_limit: None,
+                blitz: None,
+                boards: 1,
+                pack: None,
+            };
+            let mut pick = DailyPick::Today;
+            let mut lies = None;
+            while let Some((flag, value)) = flags.next()? {
+                match flag.as_str() {
+                    "--help" => return Ok(Command::Help),
+                    "--ui" => play.ui = parse_ui(&value)?,
+                    "--difficulty" => play.difficulty = value.parse()?,
+                    "--length" => play.length = Some(parse_length(&value)?),
+                    "--attempts" => {
+                        let attempts: u8 = parse_number(&flag, &value)?;
+                        if attempts == 0 {
+                            return Err(String::from("--attempts must be at least 1."));
+                        }
+                        play.attempts = Some(attempts);
+                    }
+                    "--answer-file" => play.answer_file = Some(value),
+                    "--seed" if daily => {
+                        return Err(String::from(
+                            "daily puzzles are seeded by their date, --seed can't be used with daily.",
+                        ));
+                    }
+                    "--seed" => play.seed = Some(parse_number(&flag, &value)?),
+                    "--mode" => play.mode = value.parse()?,
+                    "--lies" => {
+                        let n: u8 = parse_number(&flag, &value)?;
+                        if !(1..=2).contains(&n) {
+                            return Err(String::from("--lies must be 1 or 2."));
+                        }
+                        lies = Some(n);
+                    }
+                    "--time" => play.time_limit = Some(parse_time(&value)?),
+                    "--blitz" if daily => {
+                        return Err(String::from("--blitz is only available for 'wordle play'."));
+                    }
+                    "--blitz" => {
+                        let minutes: f64 = parse_number(&flag, &value)?;
+                        if !(minutes > 0.0 && minutes <= 24.0 * 60.0) {
+                            return Err(String::from("--blitz must be a positive number of minutes."));
+                        }
+                        play.blitz = Some(Duration::from_secs_f64(minutes * 60.0));
+                    }
+                    "--boards" if daily => {
+                        return Err(String::from("--boards is only available for 'wordle play'."));
+                    }
+                    "--boards" => play.boards = parse_boards(&value)?,
+                    "--pack" if daily => {
+                        return Err(String::from("--pack is only available for 'wordle play'."));
+                    }
+                    "--pack" => play.pack = Some(value),
+                    "--date" | "--number" if !daily => {
+                        return Err(format!("{} is only available for 'wordle daily'.", flag));
+                    }
+                    "--date" | "--number" if pick != DailyPick::Today => {
+                        return Err(String::from("Use either --date or --number, not both."));
+                    }
+                    "--date" => pick = DailyPick::Date(Date::parse(&value).map_err(|e| e.to_string())?),
+                    "--number" => pick = DailyPick::Number(parse_number(&flag, &value)?),
+                    _ => return Err(format!("Unknown option {} for '{}'.", flag, command)),
+                }
+            }
+            if let Some(n) = lies {
+                if !matches!(play.mode, GameMode::Fibble { .. }) {
+                    return Err(String::from("--lies only applies to --mode fibble."));
+                }
+                play.mode = GameMode::Fibble { lies: n };
+            }
+            // The game doesn't enforce hard mode in these, see `Game::check_guess`.
+            if play.difficulty == GameDifficulty::Hard
+                && matches!(play.mode, GameMode::Fibble { .. } | GameMode::Mastermind)
+            {
+                return Err(format!("{} can't be played on hard difficulty.", play.mode.name()));
+            }
+            if play.blitz.is_some() && play.time_limit.is_some() {
+                return Err(String::from("Use either --time or --blitz, not both."));
+            }
+            if play.boards > 1 && (play.time_limit.is_some() || play.blitz.is_some()) {
+                return Err(String::from("--time and --blitz can't be used with more than one board."));
+            }
+            if play.pack.is_some() && play.answer_file.is_some() {
+                return Err(String::from("Use either --answer-file or --pack, not both."));
+            }
+            if play.boards > 1 && play.mode != GameMode::Classic {
+                return Err(format!("{} can't be played with more than one board.", play.mode.name()));
+            }
+            Ok(if daily { Command::Daily(play, pick) } else { Command::Play(play) })
+        }
+        "stats" => {
+            let mut length = None;
+            while let Some((flag, value)) = flags.next()? {
+                match flag.as_str() {
+                    "--help" => return Ok(Command::Help),
+                    "--length" => length = Some(parse_number(&flag, &value)?),
+                    _ => return Err(format!("Unknown option {} for 'stats'.", flag)),
+                }
+            }
+            Ok(Command::Stats { length })
+        }
+        "solve" => {
+            let mut length = 5;
+            let mut answer_file = None;
+            while let Some((flag, value)) = flags.next()? {
+                match flag.as_str() {
+                    "--help" => return Ok(Command::Help),
+                    "--length" => length = parse_length(&value)?,
+                    "--answer-file" => answer_file = Some(value),
+                    _ => return Err(format!("Unknown option {} for 'solve'.", flag)),
+                }
+            }
+            Ok(Command::Solve { length, answer_file })
+        }
+        "bench" => {
+            let mut length = 5;
+            let mut strategies: Vec<String> = STRATEGY_NAMES.iter().map(|s| s.to_string()).collect();
+            let mut attempts = 6;
+            let mut limit = None;
+            let mut seed = 0;
+            let mut answer_file = None;
+            let mut matrix_mib = DEFAULT_MATRIX_MIB;
+            while let Some((flag, value)) = flags.next()? {
+                match flag.as_str() {
+                    "--help" => return Ok(Command::Help),
+                    "--length" => length = parse_length(&value)?,
+                    "--strategy" if value == "all" => {}
+                    "--strategy" if STRATEGY_NAMES.contains(&value.as_str()) => strategies = vec![value],
+                    "--strategy" => {
+                        return Err(format!(
+                            "Unknown strategy '{}', expected one of {} or all.",
+                            value,
+                            STRATEGY_NAMES.join(", ")
+                        ));
+                    }
+                    "--attempts" => {
+                        attempts = parse_number(&flag, &value)?;
+                        if attempts == 0 {
+                            return Err(String::from("--attempts must be at least 1."));
+                        }
+                    }
+                    "--limit" => limit = Some(parse_number(&flag, &value)?),
+                    "--seed" => seed = parse_number(&flag, &value)?,
+                    "--answer-file" => answer_file = Some(value),
+                    "--matrix-mib" => matrix_mib = parse_number(&flag, &value)?,
+                    _ => return Err(format!("Unknown option {} for 'bench'.", flag)),
+                }
+            }
+            Ok(Command::Bench {
+                length,
+                strategies,
+                attempts,
+                limit,
+                seed,
+                answer_file,
+                matrix_mib,
+            })
+        }
+        "query" => parse_query(&mut flags),
+        "validate-words" => {
+            let mut answer_file = None;
+            let mut dictionary_file = None;
+            while let Some((flag, value)) = flags.next()? {
+                match flag.as_str() {
+                    "--help" => return Ok(Command::Help),
+                    "--answer-file" => answer_file = Some(value),
+                    "--dictionary-file" => dictionary_file = Some(value),
+                    _ => return Err(format!("Unknown option {} for 'validate-words'.", flag)),
+                }
+            }
+            Ok(Command::ValidateWords {
+                answer_file,
+                dictionary_file,
+            })
+        }
+        "build-tiers" => {
+            let mut frequency_file = None;
+            let mut answer_file = None;
+            let mut dictionary_file = None;
+            let mut check = false;
+            while let Some((flag, value)) = flags.next()? {
+                match flag.as_str() {
+                    "--help" => return Ok(Command::Help),
+                    "--frequency-file" => frequency_file = Some(value),
+                    "--answer-file" => answer_file = Some(value),
+                    "--dictionary-file" => dictionary_file = Some(value),
+                    "--check" => {
+                        check = value
+                            .parse()
+                            .map_err(|_| format!("Invalid value '{}' for --check, expected true or false.", value))?
+                    }
+                    _ => return Err(format!("Unknown option {} for 'build-tiers'.", flag)),
+                }
+            }
+            Ok(Command::BuildTiers {
+                frequency_file,
+                answer_file,
+                dictionary_file,
+                check,
+            })
+        }
+        "build-dictionary" => {
+            let mut dictionary_file = None;
+            let mut output = None;
+            let mut check = false;
+            while let Some((flag, value)) = flags.next()? {
+                match flag.as_str() {
+                    "--help" => return Ok(Command::Help),
+                    "--dictionary-file" => dictionary_file = Some(value),
+                    "--output" => output = Some(value),
+                    "--check" => {
+                        check = value
+                            .parse()
+                            .map_err(|_| format!("Invalid value '{}' for --check, expected true or false.", value))?
+                    }
+                    _ => return Err(format!("Unknown option {} for 'build-dictionary'.", flag)),
+                }
+            }
+            Ok(Command::BuildDictionary {
+                dictionary_file,
+                output,
+                check,
+            })
+        }
+        "packs" => match flags.next()? {
+            None => Ok(Command::Packs),
+            Some((flag, _)) if flag == "--help" => Ok(Command::Help),
+            Some((flag, _)) => Err(format!("Unknown option {} for 'packs'.", flag)),
+        },
+        "help" | "-h" => Ok(Command::Help),
+        _ => Err(format!("Unknown command '{}'.", command)),
+    }
+}
+
+/// Seconds (`90`) or minutes and seconds (`1:30`).
+fn parse_time(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid time '{}' for --time, expected seconds or M:SS.", value);
+    let secs: u64 = match value.split_once(':') {
+        Some((m, s)) => {
+            let m: u64 = m.parse().map_err(|_| invalid())?;
+            let s: u64 = s.parse().map_err(|_| invalid())?;
+            if s >= 60 {
+                return Err(invalid());
+            }
+            m * 60 + s
+        }
+        None => value.parse().map_err(|_| invalid())?,
+    };
+    if secs == 0 {
+        return Err(String::from("--time must be at least one second."));
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// A word length the game can judge. Whether there are answers of that
+/// length depends on the word list, so that is checked once it is loaded.
+fn parse_length(value: &str) -> Result<usize, String> {
+    let length = parse_number("--length", value)?;
+    if length == 0 || length > MAX_PATTERN_LEN {
+        return Err(format!("--length must be between 1 and {}.", MAX_PATTERN_LEN));
+    }
+    Ok(length)
+}
+
+fn parse_boards(value: &str) -> Result<usize, String> {
+    let boards = match value.to_ascii_lowercase().as_str() {
+        "wordle" => 1,
+        "dordle" => 2,
+        "quordle" => 4,
+        "octordle" => 8,
+        _ => parse_number("--boards", value)?,
+    };
+    if boards == 0 || boards > MAX_BOARDS {
+        return Err(format!("--boards must be between 1 and {}.", MAX_BOARDS));
+    }
+    Ok(boards)
+}
+
+fn parse_letter(flag: &str, value: &str) -> Result<char, String> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_alphabetic() => Ok(c.to_lowercase().next().unwrap_or(c)),
+        _ => Err(format!("Invalid letter '{}' for {}.", value, flag)),
+    }
+}
+
+/// Splits `L=N` style values.
+fn parse_pair(flag: &str, value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(a, b)| (a.trim().to_string(), b.trim().to_string()))
+        .ok_or_else(|| format!("Invalid value '{}' for {}, expected X=Y.", value, flag))
+}
+
+fn parse_position(flag: &str, value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(p) if p >= 1 => Ok(p - 1),
+        _ => Err(format!("Invalid position '{}' for {}, positions start at 1.", value, flag)),
+    }
+}
+
+fn parse_query(flags: &mut Flags) -> Result<Command, String> {
+    let mut query = WordQuery::new(None);
+    let mut source = WordSource::Answers;
+    let mut limit = 50;
+    let mut history = Vec::new();
+    while let Some((flag, value)) = flags.next()? {
+        match flag.as_str() {
+            "--help" => return Ok(Command::Help),
+            "--length" => query.length = Some(parse_number(&flag, &value)?),
+            "--pattern" => {
+                query.length = Some(value.chars().count());
+                for (i, c) in value.chars().enumerate() {
+                    if c != '.' && c != '_' {
+                        query = query.known(i, parse_letter(&flag, &c.to_string())?);
+                    }
+                }
+            }
+            "--known" => {
+                let (position, letter) = parse_pair(&flag, &value)?;
+                query = query.known(parse_position(&flag, &position)?, parse_letter(&flag, &letter)?);
+            }
+            "--present" => {
+                let (letter, positions) = value.split_once(':').unwrap_or((&value, ""));
+                let not_at = positions
+                    .split(',')
+                    .filter(|p| !p.is_empty())
+                    .map(|p| parse_position(&flag, p.trim()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                query = query.present(parse_letter(&flag, letter)?, &not_at);
+            }
+            "--absent" => {
+                for c in value.chars() {
+                    query = query.absent(parse_letter(&flag, &c.to_string())?);
+                }
+            }
+            "--min" | "--max" => {
+                let (letter, count) = parse_pair(&flag, &value)?;
+                let letter = parse_letter(&flag, &letter)?;
+                let count = parse_number(&flag, &count)?;
+                query = if flag == "--min" {
+                    query.min_count(letter, count)
+                } else {
+                    query.max_count(letter, count)
+                };
+            }
+            "--guess" => {
+                let (word, feedback) = value
+                    .split_once(':')
+                    .ok_or_else(|| format!("Invalid value '{}' for --guess, expected WORD:FEEDBACK.", value))?;
+                let word = word.to_lowercase();
+                let result = solver::parse_feedback(feedback, word.chars().count())?;
+                history.push(GuessRecord { word, result });
+            }
+            "--source" => {
+                source = match value.to_ascii_lowercase().as_str() {
+                    "answers" | "answer" => WordSource::Answers,
+                    "dictionary" => WordSource::Dictionary,
+                    _ => return Err(format!("Unknown source '{}', expected 'answers' or 'dictionary'.", value)),
+                }
+            }
+            "--limit" => limit = parse_number(&flag, &value)?,
+            _ => return Err(format!("Unknown option {} for 'query'.", flag)),
+        }
+    }
+    if let Some(first) = history.first() {
+        let length = first.word.chars().count();
+        if query.length.is_some_and(|len| len != length)
+            || history.iter().any(|r| r.word.chars().count() != length)
+        {
+            return Err(String::from("All guesses and --length/--pattern must have the same length."));
+        }
+        let revealed = WordQuery::from_history(length, &history);
+        query.length = Some(length);
+        query.known.extend(revealed.known);
+        for (letter, not_at) in revealed.present {
+            query = query.present(letter, &not_at);
+        }
+        for letter in revealed.absent {
+            query = query.absent(letter);
+        }
+        for (letter, count) in revealed.min_counts {
+            query = query.min_count(letter, count);
+        }
+        for (letter, count) in revealed.max_counts {
+            query = query.max_count(letter, count);
+        }
+    }
+    if let Some(length) = query.length
+        && let Some(&(position, _)) = query.known.iter().find(|(p, _)| *p >= length)
+    {
+        return Err(format!("Position {} is outside a {} letter word.", position + 1, length));
+    }
+    Ok(Command::Query { query, source, limit })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_line(line: &str) -> Result<Command, String> {
+        let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+        parse(&args)
+    }
+
+    fn play(line: &str) -> PlayArgs {
+        match parse_line(line) {
+            Ok(Command::Play(play)) => play,
+            other => panic!("{}: {:?}", line, other),
+        }
+    }
+
+    #[test]
+    fn play_is_the_default_command() {
+        assert_eq!(parse_line(""), parse_line("play"));
+        let args = play("--ui plain --difficulty hard");
+        assert_eq!(args.ui, ConsoleUiType::Plain);
+        assert_eq!(args.difficulty, GameDifficulty::Hard);
+    }
+
+    #[test]
+    fn play_options_are_parsed() {
+        let args = play("play --ui=tui --length 6 --attempts 8 --seed 42 --answer-file words.json --time 1:30");
+        assert_eq!(args.ui, ConsoleUiType::Ratatui);
+        assert_eq!(args.difficulty, GameDifficulty::Easy);
+        assert_eq!(args.length, Some(6));
+        assert_eq!(args.attempts, Some(8));
+        assert_eq!(args.seed, Some(42));
+        assert_eq!(args.answer_file.as_deref(), Some("words.json"));
+        assert_eq!(args.time_limit, Some(Duration::from_secs(90)));
+        assert_eq!(play("--mode fibble --lies 2").mode, GameMode::Fibble { lies: 2 });
+        assert_eq!(play("--boards quordle").boards, 4);
+        assert_eq!(play("--blitz 1.5").blitz, Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn daily_options_are_parsed() {
+        match parse_line("daily --number 12 --ui plain") {
+            Ok(Command::Daily(args, DailyPick::Number(12))) => {
+                assert_eq!(args.ui, ConsoleUiType::Plain);
+                assert_eq!(args.difficulty, GameDifficulty::Medium);
+            }
+            other => panic!("{:?}", other),
+        }
+        let date = Date::parse("2025-03-01").unwrap();
+        assert_eq!(
+            parse_line("daily --date 2025-03-01"),
+            Ok(Command::Daily(play("play --difficulty medium"), DailyPick::Date(date)))
+        );
+    }
+
+    #[test]
+    fn other_commands_are_parsed() {
+        assert_eq!(parse_line("stats --length 5"), Ok(Command::Stats { length: Some(5) }));
+        assert_eq!(
+            parse_line("solve --length 6"),
+            Ok(Command::Solve {
+                length: 6,
+                answer_file: None
+            })
+        );
+        assert_eq!(
+            parse_line("validate-words --dictionary-file d.json"),
+            Ok(Command::ValidateWords {
+                answer_file: None,
+                dictionary_file: Some(String::from("d.json")),
+            })
+        );
+        assert_eq!(
+            parse_line("bench --strategy entropy --limit 10 --matrix-mib 128"),
+            Ok(Command::Bench {
+                length: 5,
+                strategies: vec![String::from("entropy")],
+                attempts: 6,
+                limit: Some(10),
+                seed: 0,
+                answer_file: None,
+                matrix_mib: 128,
+            })
+        );
+        assert_eq!(parse_line("packs"), Ok(Command::Packs));
+        assert_eq!(parse_line("help"), Ok(Command::Help));
+        assert_eq!(parse_line("play --help"), Ok(Command::Help));
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        let rejected = [
+            ("bogus", "Unknown command 'bogus'."),
+            ("play --colour red", "Unknown option --colour for 'play'."),
+            ("play --ui gui", "Unknown ui 'gui', expected 'plain' or 'tui'."),
+            ("play --mode chess", "Unknown mode: chess"),
+            ("play --length five", "Invalid value 'five' for --length, expected a number."),
+            ("play --length 0", "--length must be between 1 and 20."),
+            ("play --length 21", "--length must be between 1 and 20."),
+            ("solve --length 99", "--length must be between 1 and 20."),
+            ("play --attempts 0", "--attempts must be at least 1."),
+            ("play --lies 3 --mode fibble", "--lies must be 1 or 2."),
+            ("play --time 0", "--time must be at least one second."),
+            ("play --time 1:75", "Invalid time '1:75' for --time, expected seconds or M:SS."),
+            ("play --boards 99", "--boards must be between 1 and 16."),
+            ("play --seed", "Missing value for --seed."),
+            ("play extra", "Unexpected argument 'extra'."),
+        ];
+        for (line, error) in rejected {
+            assert_eq!(parse_line(line), Err(String::from(error)), "{}", line);
+        }
+    }
+
+    #[test]
+    fn invalid_combinations_are_rejected() {
+        let rejected = [
+            ("daily --seed 1", "daily puzzles are seeded by their date, --seed can't be used with daily."),
+            ("daily --blitz 3", "--blitz is only available for 'wordle play'."),
+            ("daily --boards 2", "--boards is only available for 'wordle play'."),
+            ("daily --pack programming", "--pack is only available for 'wordle play'."),
+            ("play --number 3", "--number is only available for 'wordle daily'."),
+            ("daily --date 2025-01-02 --number 3", "Use either --date or --number, not both."),
+            ("play --lies 2", "--lies only applies to --mode fibble."),
+            ("play --mode fibble --difficulty hard", "Fibble can't be played on hard difficulty."),
+            ("daily --mode mastermind --difficulty hard", "Mastermind can't be played on hard difficulty."),
+            ("play --time 60 --blitz 3", "Use either --time or --blitz, not both."),
+            ("play --boards 2 --time 60", "--time and --blitz can't be used with more than one board."),
+            ("play --pack programming --answer-file a.json", "Use either --answer-file or --pack, not both."),
+            ("play --boards 2 --mode absurdle", "Absurdle can't be played with more than one board."),
+        ];
+        for (line, error) in rejected {
+            assert_eq!(parse_line(line), Err(String::from(error)), "{}", line);
+        }
+    }
+}
@@ -0,0 +1,473 @@
+use std::io::{Write, stdout};
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::{
+    cli::args::{self, Command, DailyPick, PlayArgs},
+    game::{
+        blitz::Blitz,
+        daily::Daily,
+        game::{Game, GameOptions},
+        multi::MultiGame,
+        solver::{self, Solver},
+        strategy,
+    },
+    stats::stats::{Statistics, Summary},
+    types::*,
+    ui::console::{
+        multi_plain::App as MultiConsoleGame, multi_ratatui::App as MultiRatatuiGame,
+        pack_menu::{self, Choice}, plain_console::App as ConsoleGame, ratatui::App as RatatuiGame,
+    },
+    word::{
+        dictionary::{self, Dictionary},
+        pack::{self, Pack},
+        query::{WordQuery, WordSource},
+        tiers::{self, Tiers},
+        word::{self, AnswerSource, Word},
+    },
+};
+
+/// Runs the command line and returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let command = match args::parse(args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {}\n\nRun 'wordle help' for usage.", err);
+            return 2;
+        }
+    };
+    let result = match command {
+        Command::Play(play) => run_play(&play, None),
+        Command::Daily(play, pick) => run_daily(&play, pick),
+        Command::Stats { length } => {
+            run_stats(length);
+            Ok(())
+        }
+        Command::Solve { length, answer_file } => run_solve(length, answer_file),
+        Command::Bench {
+            length,
+            strategies,
+            attempts,
+            limit,
+            seed,
+            answer_file,
+            matrix_mib,
+        } => run_bench(length, &strategies, attempts, limit, seed, answer_file, matrix_mib),
+        Command::Query { query, source, limit } => run_query(&query, source, limit),
+        Command::ValidateWords {
+            answer_file,
+            dictionary_file,
+        } => run_validate(answer_file, dictionary_file),
+        Command::BuildTiers {
+            frequency_file,
+            answer_file,
+            dictionary_file,
+            check,
+        } => run_build_tiers(frequency_file, answer_file, dictionary_file, check),
+        Command::BuildDictionary {
+            dictionary_file,
+            output,
+            check,
+        } => run_build_dictionary(dictionary_file, output, check),
+        Command::Packs => run_packs(),
+        Command::Help => {
+            print!("{}", args::USAGE);
+            Ok(())
+        }
+    };
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            1
+        }
+    }
+}
+
+/// Loads the given files, falling back to the `default_answers` list and the
+/// default dictionary (see `word::read_list` and `word::read_dictionary`).
+fn load_words(answer_file: Option<&str>, dictionary_file: Option<&str>, default_answers: &str) -> Result<Word, String> {
+    let source = match answer_file {
+        // Absolute, so a game saved with it can be resumed from anywhere.
+        Some(path) => AnswerSource::File(
+            std::fs::canonicalize(path).map_or_else(|_| path.to_string(), |p| p.to_string_lossy().to_string()),
+        ),
+        None => AnswerSource::List(default_answers.to_string()),
+    };
+    let words = match dictionary_file {
+        Some(path) => Dictionary::from_file(Path::new(path))
+            .and_then(|dictionary| Word::with_dictionary(&source.read()?, dictionary))
+            .map(|words| words.with_source(source)),
+        None => Word::from_source(&source),
+    };
+    words.map_err(|err| format!("Failed to load word lists: {}", err))
+}
+
+fn run_play(play: &PlayArgs, daily: Option<Daily>) -> Result<(), String> {
+    let answers = word::game_list(&play.difficulty, daily.is_some());
+    let mut words = load_words(play.answer_file.as_deref(), None, answers)?;
+    let pack = match &play.pack {
+        Some(id) => Some(pack::find(id).map_err(|err| err.to_string())?),
+        None if play.ui == ConsoleUiType::Ratatui && daily.is_none() && play.answer_file.is_none() => {
+            let (packs, broken): (Vec<_>, Vec<_>) = pack::discover().into_iter().partition(Result::is_ok);
+            let packs: Vec<Pack> = packs.into_iter().filter_map(Result::ok).collect();
+            if packs.is_empty() {
+                None
+            } else {
+                match pack_menu::choose(&packs, broken.len()) {
+                    Choice::BuiltIn => None,
+                    Choice::Pack(pack) => Some(pack.clone()),
+                    Choice::Quit => return Ok(()),
+                }
+            }
+        }
+        None => None,
+    };
+    if let Some(pack) = &pack {
+        words = words.with_pack(pack);
+    }
+    if let Some(length) = play.length {
+        let lengths = words.answer_lengths();
+        if !lengths.contains(&length) {
+            return Err(format!(
+                "No answers of length {}. Available lengths: {}.",
+                length,
+                lengths.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(", ")
+            ));
+        }
+    }
+    if play.boards > 1 {
+        return run_multi(play, Rc::new(words));
+    }
+    let options = GameOptions {
+        difficulty: Some(play.difficulty),
+        max_attempts: play.attempts,
+        word_length: play.length,
+        seed: play.seed,
+        daily,
+        time_limit: play.time_limit,
+        mode: play.mode,
+        ..Default::default()
+    };
+    if let Some(limit) = play.blitz {
+        let blitz = Blitz::new(Rc::new(words), limit, options);
+        match play.ui {
+            ConsoleUiType::Plain => ConsoleGame::from_blitz(blitz).start(),
+            ConsoleUiType::Ratatui => RatatuiGame::from_blitz(blitz).start(),
+        }
+        return Ok(());
+    }
+    let game = Game::with_word(Rc::new(words), options);
+    match play.ui {
+        ConsoleUiType::Plain => ConsoleGame::from_game(game).start(),
+        ConsoleUiType::Ratatui => RatatuiGame::from_game(game).start(),
+    }
+    Ok(())
+}
+
+fn run_multi(play: &PlayArgs, words: Rc<Word>) -> Result<(), String> {
+    let game = MultiGame::with_word(
+        words,
+        play.boards,
+        GameOptions {
+            difficulty: Some(play.difficulty),
+            max_attempts: play.attempts,
+            word_length: play.length,
+            seed: play.seed,
+            ..Default::default()
+        },
+    )
+    .map_err(|err| err.to_string())?;
+    match play.ui {
+        ConsoleUiType::Plain => MultiConsoleGame::from_game(game).start(),
+        ConsoleUiType::Ratatui => MultiRatatuiGame::from_game(game).start(),
+    }
+    Ok(())
+}
+
+fn run_daily(play: &PlayArgs, pick: DailyPick) -> Result<(), String> {
+    let daily = match pick {
+        DailyPick::Today => Daily::today(),
+        DailyPick::Date(date) => Daily::from_date(date),
+        DailyPick::Number(number) => Daily::from_number(number),
+    }
+    .map_err(|err| err.to_string())?;
+    run_play(play, Some(daily))
+}
+
+fn print_summary(summary: &Summary) {
+    let secs = summary.total_duration.as_secs();
+    println!(
+        "{}  Time played {}:{:02}:{:02}",
+        summary,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    );
+    let widest = summary.distribution.iter().copied().max().unwrap_or(0).max(1);
+    for (i, count) in summary.distribution.iter().enumerate() {
+        let width = (*count as usize * 30).div_ceil(widest as usize);
+        println!("  {:>2} {} {}", i + 1, "#".repeat(width), count);
+    }
+}
+
+fn run_stats(length: Option<usize>) {
+    let statistics = Statistics::load();
+    if statistics.records().is_empty() {
+        println!("No finished games yet.");
+        return;
+    }
+    for (i, (mode, blitz)) in statistics.modes().into_iter().enumerate() {
+        let mut title = match mode {
+            GameMode::Fibble { lies } if lies > 1 => format!("{} ({} lies)", mode.name(), lies),
+            _ => mode.name().to_string(),
+        };
+        if blitz {
+            title.push_str(" blitz");
+        }
+        if i > 0 {
+            println!();
+        }
+        match length {
+            Some(length) => {
+                println!("{}, {} letters:", title, length);
+                print_summary(&statistics.summary(mode, blitz, Some(length)));
+            }
+            None => {
+                println!("{}, all games:", title);
+                print_summary(&statistics.summary(mode, blitz, None));
+                for length in statistics.word_lengths(mode, blitz) {
+                    println!("\n{}, {} letters:", title, length);
+                    print_summary(&statistics.summary(mode, blitz, Some(length)));
+                }
+            }
+        }
+    }
+}
+
+fn run_solve(length: usize, answer_file: Option<String>) -> Result<(), String> {
+    let words = load_words(answer_file.as_deref(), None, word::ANSWER_LIST)?;
+    if !words.answer_lengths().contains(&length) {
+        return Err(format!("No answers of length {}.", length));
+    }
+    let mut solver = Solver::new(&words, length);
+    println!("After each guess enter the word and its feedback, e.g. 'crane gy..g'.");
+    println!("Feedback uses g for green, y for yellow and . for gray. Enter :q to quit.");
+    loop {
+        let candidates = solver.candidates();
+        match candidates.len() {
+            0 => {
+                println!("No candidates left, check the feedback you entered.");
+                return Ok(());
+            }
+            1 => {
+                println!("The answer is {}.", candidates[0]);
+                return Ok(());
+            }
+            n if n <= 10 => println!("{} candidates: {}", n, candidates.join(", ")),
+            n => println!("{} candidates", n),
+        }
+        println!("Suggestions:");
+        for guess in solver.rank_guesses(5) {
+            println!("  {}", guess);
+        }
+        print!("> ");
+        stdout().flush().expect("Failed to flush.");
+        let mut line = String::new();
+        let read = std::io::stdin()
+            .read_line(&mut line)
+            .expect("Failed to read line");
+        let line = line.trim();
+        if read == 0 || line == ":q" || line == ":quit" {
+            return Ok(());
+        }
+        let (guess, feedback) = match line.split_once(char::is_whitespace) {
+            Some((guess, feedback)) => (guess.to_lowercase(), feedback.trim()),
+            None => {
+                println!("Enter the guess and its feedback separated by a space.");
+                continue;
+            }
+        };
+        if guess.chars().count() != length {
+            println!("Guess must have {} letters.", length);
+            continue;
+        }
+        match solver::parse_feedback(feedback, length) {
+            Ok(result) => solver.apply(&guess, &result),
+            Err(err) => println!("{}", err),
+        }
+    }
+}
+
+fn run_bench(
+    length: usize,
+    strategies: &[String],
+    attempts: u8,
+    limit: Option<usize>,
+    seed: u64,
+    answer_file: Option<String>,
+    matrix_mib: usize,
+) -> Result<(), String> {
+    let words = Rc::new(load_words(answer_file.as_deref(), None, word::ANSWER_LIST)?);
+    if !words.answer_lengths().contains(&length) {
+        return Err(format!("No answers of length {}.", length));
+    }
+    for name in strategies {
+        let mut strategy = strategy::strategy_by_name(name, seed)
+            .ok_or_else(|| format!("Unknown strategy '{}'.", name))?;
+        let started = std::time::Instant::now();
+        let report = strategy::benchmark(&words, length, strategy.as_mut(), attempts, limit, matrix_mib);
+        println!(
+            "{:<10} games {:>5}  average {:.3}  failures {:>4}  ({:.1}s)",
+            report.strategy,
+            report.games,
+            report.average_guesses(),
+            report.failures.len(),
+            started.elapsed().as_secs_f64()
+        );
+        let worst: Vec<String> = report
+            .worst
+            .iter()
+            .map(|(answer, guesses)| {
+                if *guesses > attempts {
+                    format!("{} (X)", answer)
+                } else {
+                    format!("{} ({})", answer, guesses)
+                }
+            })
+            .collect();
+        println!("{:<10} worst: {}", "", worst.join(", "));
+    }
+    Ok(())
+}
+
+fn run_query(query: &WordQuery, source: WordSource, limit: usize) -> Result<(), String> {
+    let words = load_words(None, None, word::ANSWER_LIST)?;
+    let matches = words.query(query, source);
+    for word in matches.iter().take(limit) {
+        println!("{}", word);
+    }
+    if matches.len() > limit {
+        println!("... and {} more", matches.len() - limit);
+    }
+    println!("{} matching word(s)", matches.len());
+    Ok(())
+}
+
+fn run_validate(answer_file: Option<String>, dictionary_file: Option<String>) -> Result<(), String> {
+    let words = load_words(answer_file.as_deref(), dictionary_file.as_deref(), word::ANSWER_LIST)?;
+    for length in words.answer_lengths() {
+        println!("{:>2} letters: {} answers", length, words.answer[length].len());
+    }
+    println!("Dictionary: {} words", words.dictionary.len());
+    let problems = words.validate();
+    if problems.is_empty() {
+        println!("All answers are valid.");
+        return Ok(());
+    }
+    for problem in &problems {
+        println!("  {}", problem);
+    }
+    Err(format!("Found {} problem(s) in the word lists.", problems.len()))
+}
+
+fn run_packs() -> Result<(), String> {
+    println!("Packs are read from (first match wins):");
+    for dir in crate::paths::pack_dirs() {
+        println!("  {}", dir.display());
+    }
+    let packs = pack::discover();
+    if packs.is_empty() {
+        println!("No word packs installed.");
+        return Ok(());
+    }
+    let mut broken = 0;
+    for pack in &packs {
+        match pack {
+            Ok(pack) => println!("{:<16} {}", pack.id, pack),
+            Err(err) => {
+                broken += 1;
+                println!("error: {}", err);
+            }
+        }
+    }
+    if broken > 0 {
+        return Err(format!("{} of {} pack(s) failed to load.", broken, packs.len()));
+    }
+    Ok(())
+}
+
+fn run_build_tiers(
+    frequency_file: Option<String>,
+    answer_file: Option<String>,
+    dictionary_file: Option<String>,
+    check: bool,
+) -> Result<(), String> {
+    // A maintenance command: it reads and writes the source tree, not the
+    // lists compiled into the binary.
+    let answer_file = answer_file.unwrap_or_else(|| format!("{}/{}", word::ASSET_DIR, word::ANSWER_LIST));
+    let dictionary_file =
+        dictionary_file.unwrap_or_else(|| format!("{}/{}", word::ASSET_DIR, word::DICTIONARY_LIST));
+    let words = Word::load(&answer_file, &dictionary_file).map_err(|err| format!("Failed to load word lists: {}", err))?;
+    let problems = words.validate();
+    if !problems.is_empty() {
+        for problem in &problems {
+            println!("  {}", problem);
+        }
+        return Err(format!("Found {} problem(s) in the word lists, tiers not written.", problems.len()));
+    }
+    let ranks = match &frequency_file {
+        Some(path) => {
+            let ranks = tiers::load_frequency(path).map_err(|err| format!("{}: {}", path, err))?;
+            let unranked = words.answer.iter().flatten().filter(|w| !ranks.contains_key(*w)).count();
+            if unranked > 0 {
+                println!("{} answer(s) are missing from {} and count as the least common.", unranked, path);
+            }
+            ranks
+        }
+        None => tiers::list_ranks(&words.answer),
+    };
+    let tiers = Tiers::split(&words.answer, &ranks);
+    let mut stale = Vec::new();
+    for difficulty in [GameDifficulty::Easy, GameDifficulty::Medium, GameDifficulty::Hard] {
+        let lists = tiers.get(&difficulty);
+        let path = format!("{}/{}", word::ASSET_DIR, word::answer_list(&difficulty));
+        let json = tiers::answers_to_json(lists) + "\n";
+        let count: usize = lists.iter().map(Vec::len).sum();
+        if check {
+            if std::fs::read_to_string(&path).ok().as_deref() != Some(json.as_str()) {
+                stale.push(path);
+            }
+            continue;
+        }
+        std::fs::write(&path, json).map_err(|err| format!("Failed to write {}: {}", path, err))?;
+        println!("{:<6} {} answers -> {}", difficulty, count, path);
+    }
+    if !stale.is_empty() {
+        return Err(format!("Out of date: {}. Run 'wordle build-tiers' to regenerate.", stale.join(", ")));
+    }
+    if check {
+        println!("Tiers are up to date.");
+    }
+    Ok(())
+}
+
+fn run_build_dictionary(dictionary_file: Option<String>, output: Option<String>, check: bool) -> Result<(), String> {
+    // Like build-tiers, this works on the source tree.
+    let dictionary_file =
+        dictionary_file.unwrap_or_else(|| format!("{}/{}", word::ASSET_DIR, word::DICTIONARY_LIST));
+    let output = output.unwrap_or_else(|| format!("{}/{}", word::ASSET_DIR, word::DICTIONARY_BIN));
+    let json = std::fs::read_to_string(&dictionary_file).map_err(|err| format!("{}: {}", dictionary_file, err))?;
+    let words = dictionary::parse_json(&json).map_err(|err| format!("{}: {}", dictionary_file, err))?;
+    let bytes = dictionary::encode(words.iter().map(String::as_str));
+    if check {
+        if std::fs::read(&output).ok() != Some(bytes) {
+            return Err(format!("Out of date: {}. Run 'wordle build-dictionary' to regenerate.", output));
+        }
+        println!("Dictionary is up to date.");
+        return Ok(());
+    }
+    std::fs::write(&output, &bytes).map_err(|err| format!("Failed to write {}: {}", output, err))?;
+    println!("{} words, {} bytes -> {}", words.len(), bytes.len(), output);
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::{
+    game::game::{Game, GameOptions},
+    types::*,
+    word::word::Word,
+};
+
+/// Solve as many puzzles in a row as possible before the clock runs out.
+/// A run ends when the time is up or a puzzle is lost. Every puzzle is a
+/// normal `Game` whose time limit is whatever is left of the run.
+pub struct Blitz {
+    dictionary: Rc<Word>,
+    options: GameOptions,
+    limit: Duration,
+    seed: u64,
+    started_at: Option<Instant>,
+    played: u64,
+    solved: u32,
+    guesses: u32,
+}
+
+impl Blitz {
+    /// `options` is used for every puzzle. Its seed, if any, seeds the whole
+    /// run; each puzzle gets the next seed after it.
+    pub fn new(dictionary: Rc<Word>, limit: Duration, options: GameOptions) -> Self {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        Blitz {
+            dictionary,
+            options,
+            limit,
+            seed,
+            started_at: None,
+            played: 0,
+            solved: 0,
+            guesses: 0,
+        }
+    }
+    /// Starts the clock on the first call. Returns the next puzzle, already
+    /// started, or `None` once the run is over.
+    pub fn next_game(&mut self) -> Option<Game> {
+        let started_at = *self.started_at.get_or_insert_with(Instant::now);
+        let remaining = self.limit.saturating_sub(started_at.elapsed());
+        if remaining.is_zero() || self.solved as u64 != self.played {
+            return None;
+        }
+        let mut game = Game::with_word(
+            Rc::clone(&self.dictionary),
+            GameOptions {
+                seed: Some(self.seed.wrapping_add(self.played)),
+                time_limit: Some(remaining),
+                daily: None,
+                ..self.options.clone()
+            },
+        );
+        self.played += 1;
+        game.start();
+        Some(game)
+    }
+    /// Counts a finished puzzle towards the run.
+    pub fn record(&mut self, game: &Game) {
+        self.guesses += game.get_attempts() as u32;
+        if game.get_state() == GameState::Over(GameResult::Won) {
+            self.solved += 1;
+        }
+    }
+    /// Time left in the run.
+    pub fn remaining(&self) -> Duration {
+        match self.started_at {
+            Some(started_at) => self.limit.saturating_sub(started_at.elapsed()),
+            None => self.limit,
+        }
+    }
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+    /// Puzzles solved so far.
+    pub fn solved(&self) -> u32 {
+        self.solved
+    }
+    /// Guesses spent over the whole run.
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+}
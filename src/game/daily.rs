@@ -0,0 +1,290 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{types::AnswerError, word::word::Word};
+
+/// The date of puzzle #1.
+const FIRST_PUZZLE: Date = Date {
+    year: 2025,
+    month: 1,
+    day: 1,
+};
+/// Mixed into the puzzle number so daily seeds don't collide with small
+/// hand-picked seeds.
+const DAILY_SALT: u64 = 0x5745_4f52_444c_4531;
+/// How often each answer length is picked when the daily length isn't set,
+/// in thousandths; the same odds as `Word::generate_answer`.
+const LENGTH_WEIGHTS: [(usize, u64); 12] = [
+    (4, 200),
+    (5, 400),
+    (6, 200),
+    (7, 100),
+    (8, 50),
+    (9, 30),
+    (10, 10),
+    (11, 2),
+    (12, 2),
+    (13, 2),
+    (14, 2),
+    (15, 2),
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Today's date in UTC, so everyone gets the same puzzle at the same moment.
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Date::from_days((secs / 86_400) as i64)
+    }
+    /// Parses `YYYY-MM-DD`.
+    pub fn parse(s: &str) -> Result<Self, DailyError> {
+        let invalid = || DailyError::InvalidDate(s.to_string());
+        let mut parts = s.trim().split('-');
+        let year = parts.next().and_then(|p| p.parse().ok()).ok_or_else(invalid)?;
+        let month = parts.next().and_then(|p| p.parse().ok()).ok_or_else(invalid)?;
+        let day = parts.next().and_then(|p| p.parse().ok()).ok_or_else(invalid)?;
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        let date = Date { year, month, day };
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(invalid());
+        }
+        Ok(date)
+    }
+    /// Days since 1970-01-01.
+    pub fn to_days(self) -> i64 {
+        // Howard Hinnant's days_from_civil.
+        let y = if self.month <= 2 { self.year - 1 } else { self.year } as i64;
+        let m = self.month as i64;
+        let d = self.day as i64;
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+    pub fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Date { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DailyError {
+    InvalidDate(String),
+    BeforeFirstPuzzle,
+    InFuture,
+}
+
+impl fmt::Display for DailyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDate(s) => write!(f, "Invalid date '{}', expected YYYY-MM-DD.", s),
+            Self::BeforeFirstPuzzle => {
+                write!(f, "The first daily puzzle was on {}.", FIRST_PUZZLE)
+            }
+            Self::InFuture => f.write_str("That puzzle hasn't been released yet."),
+        }
+    }
+}
+
+impl std::error::Error for DailyError {}
+
+/// A daily puzzle. Everyone playing the same puzzle number (with the same
+/// difficulty and length setting) gets the same answer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Daily {
+    number: u32,
+}
+
+impl Daily {
+    /// Today's puzzle, or `BeforeFirstPuzzle` if the system clock says the
+    /// first one isn't out yet.
+    pub fn today() -> Result<Self, DailyError> {
+        Self::from_date(Date::today())
+    }
+    pub fn from_date(date: Date) -> Result<Self, DailyError> {
+        let offset = date.to_days() - FIRST_PUZZLE.to_days();
+        if offset < 0 {
+            return Err(DailyError::BeforeFirstPuzzle);
+        }
+        if date > Date::today() {
+            return Err(DailyError::InFuture);
+        }
+        Ok(Daily {
+            number: offset as u32 + 1,
+        })
+    }
+    pub fn from_number(number: u32) -> Result<Self, DailyError> {
+        if number == 0 {
+            return Err(DailyError::BeforeFirstPuzzle);
+        }
+        let daily = Daily { number };
+        if daily.date() > Date::today() {
+            return Err(DailyError::InFuture);
+        }
+        Ok(daily)
+    }
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+    pub fn date(&self) -> Date {
+        Date::from_days(FIRST_PUZZLE.to_days() + self.number as i64 - 1)
+    }
+    /// The seed behind `answer` and, in Fibble, the lies.
+    pub fn seed(&self) -> u64 {
+        // So consecutive days get unrelated seeds.
+        splitmix64((self.number as u64) ^ DAILY_SALT)
+    }
+    /// This puzzle's answer from `words`, of `word_length` letters or a
+    /// length picked with `LENGTH_WEIGHTS`. Doesn't use `rand`, whose
+    /// generators and sampling may change between versions: a puzzle must
+    /// keep its answer for good.
+    pub fn answer(&self, words: &Word, word_length: Option<usize>) -> Result<String, AnswerError> {
+        let mut state = self.seed();
+        let mut next = || {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            splitmix64(state)
+        };
+        let word_length = match word_length {
+            Some(len) => len,
+            None => pick_length(&words.answer_lengths(), next()),
+        };
+        let candidates = words.answer.get(word_length).ok_or(AnswerError::InvalidLength(word_length))?;
+        if candidates.is_empty() {
+            return Err(AnswerError::NoWords(word_length));
+        }
+        Ok(candidates[(next() % candidates.len() as u64) as usize].clone())
+    }
+}
+
+/// The length `draw` picks from `LENGTH_WEIGHTS` among `lengths`, the ones
+/// with answers. Lengths without a weight are only picked, evenly, when
+/// there are no others.
+fn pick_length(lengths: &[usize], draw: u64) -> usize {
+    let weights: Vec<(usize, u64)> = LENGTH_WEIGHTS
+        .iter()
+        .copied()
+        .filter(|(len, _)| lengths.contains(len))
+        .collect();
+    let total: u64 = weights.iter().map(|(_, weight)| weight).sum();
+    if total == 0 {
+        // With no lengths at all, any length reports that there are no words.
+        return lengths.get((draw % lengths.len().max(1) as u64) as usize).copied().unwrap_or(5);
+    }
+    let mut roll = draw % total;
+    for (len, weight) in weights {
+        if roll < weight {
+            return len;
+        }
+        roll -= weight;
+    }
+    unreachable!("roll is below the total weight")
+}
+
+/// splitmix64's output function.
+fn splitmix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::GameDifficulty,
+        word::word::{self, AnswerSource},
+    };
+
+    fn words() -> Word {
+        let answers = r#"{
+            "4": ["bark", "cove", "dune", "fern"],
+            "5": ["apple", "brave", "crane", "drift", "eagle", "flame", "grape", "heart"],
+            "6": ["basket", "candle", "dragon", "forest"]
+        }"#;
+        Word::from_json(answers, "{}").unwrap()
+    }
+
+    #[test]
+    fn answers_never_change() {
+        // Pinned: if this fails, every past daily puzzle has a new answer.
+        let words = words();
+        let picks: Vec<String> = (1..=6)
+            .map(|n| Daily { number: n }.answer(&words, None).unwrap())
+            .collect();
+        assert_eq!(picks, ["eagle", "eagle", "candle", "apple", "cove", "crane"]);
+        assert_eq!(Daily { number: 42 }.answer(&words, Some(6)).unwrap(), "dragon");
+    }
+
+    #[test]
+    fn bundled_answers_never_change() {
+        // The list `wordle daily` plays, whatever the difficulty. Pinned for
+        // the same reason as above, and so rebuilding the tiers can't move it.
+        let list = word::game_list(&GameDifficulty::Hard, true);
+        let words = Word::from_source(&AnswerSource::List(list.to_string())).unwrap();
+        let picks: Vec<String> = (1..=6)
+            .map(|n| Daily { number: n }.answer(&words, None).unwrap())
+            .collect();
+        assert_eq!(picks, ["crowding", "repeat", "deify", "vibe", "creed", "learner"]);
+    }
+
+    #[test]
+    fn answer_reports_missing_lengths() {
+        let words = words();
+        let daily = Daily { number: 1 };
+        assert_eq!(daily.answer(&words, Some(3)), Err(AnswerError::NoWords(3)));
+        assert_eq!(daily.answer(&words, Some(40)), Err(AnswerError::InvalidLength(40)));
+    }
+
+    #[test]
+    fn pick_length_only_picks_lengths_with_answers() {
+        for draw in 0..1000 {
+            assert!([5, 6].contains(&pick_length(&[5, 6], draw)));
+            assert_eq!(pick_length(&[3], draw), 3);
+        }
+    }
+
+    #[test]
+    fn dates_round_trip_through_days() {
+        for days in [-1, 0, 59, 365, 20_089, 20_454, 100_000] {
+            assert_eq!(Date::from_days(days).to_days(), days);
+        }
+        assert_eq!(Date::parse("2025-01-01").unwrap().to_days(), 20_089);
+        assert!(Date::parse("2025-02-29").is_err());
+        assert_eq!(Date::parse("2024-02-29").unwrap().to_string(), "2024-02-29");
+    }
+}
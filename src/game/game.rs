@@ -1,11 +1,61 @@
-use crate::{types::*, word::word::Word};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::index};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::{
+    game::{
+        daily::Daily,
+        judge::{self, Pattern},
+        solver::Solver,
+    },
+    types::*,
+    word::word::Word,
+};
+
+/// Everything needed to set up a game. Unset fields fall back to the same
+/// defaults as `Game::new`.
+#[derive(Debug, Clone, Default)]
+pub struct GameOptions {
+    pub answer: Option<String>,
+    pub difficulty: Option<GameDifficulty>,
+    pub max_attempts: Option<u8>,
+    /// Fixes the answer length. When `None` the length is rolled from the seed.
+    pub word_length: Option<usize>,
+    pub seed: Option<u64>,
+    /// Plays a daily puzzle. Overrides `seed`.
+    pub daily: Option<Daily>,
+    /// Total time allowed for the game. Running out loses it.
+    pub time_limit: Option<Duration>,
+    /// In `GameMode::Absurdle` the seed only picks the word length and
+    /// `answer` is ignored. In `GameMode::Fibble` the seed also decides
+    /// which tiles lie.
+    pub mode: GameMode,
+}
+
 pub struct Game {
     state: GameState,
+    difficulty: GameDifficulty,
     word_length: usize,
     max_attempts: u8,
-    guess_count: u8,
-    answer: String,
-    dictionary: Word,
+    mode: GameMode,
+    /// `None` while an Absurdle host hasn't settled on a word yet.
+    answer: Option<String>,
+    /// Answers an Absurdle host can still pick. Empty in other modes.
+    candidates: Vec<String>,
+    seed: Option<u64>,
+    daily: Option<Daily>,
+    dictionary: Rc<Word>,
+    history: Vec<GuessRecord>,
+    /// Fibble: the positions that lied in each row of `history`.
+    lies: Vec<Vec<usize>>,
+    hints: Vec<Hint>,
+    loss_reason: Option<LossReason>,
+    time_limit: Option<Duration>,
+    /// Play time banked before `started_at`, e.g. from a resumed save.
+    elapsed: Duration,
+    started_at: Option<Instant>,
 }
 
 impl Game {
@@ -13,74 +63,413 @@ impl Game {
         answer: Option<String>,
         difficulty: Option<GameDifficulty>,
         max_attempts: Option<u8>,
-    ) -> Self {
-        let difficulty = difficulty.unwrap_or(GameDifficulty::Medium);
-        let dictionary = Word::new(&difficulty);
-        let answer = match answer {
-            Some(a) => a,
-            None => {
-                dictionary
-                    .generate_answer(None)
-                    .expect("Failed to generate answer.")
+        seed: Option<u64>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_options(GameOptions {
+            answer,
+            difficulty,
+            max_attempts,
+            seed,
+            ..Default::default()
+        })
+    }
+    pub fn with_options(options: GameOptions) -> Result<Self, Box<dyn std::error::Error>> {
+        let difficulty = options.difficulty.unwrap_or(GameDifficulty::Medium);
+        Ok(Self::with_word(Rc::new(Word::new(&difficulty)?), options))
+    }
+    /// Like `with_options`, but reuses already loaded word lists.
+    pub fn with_word(dictionary: Rc<Word>, options: GameOptions) -> Self {
+        let difficulty = options.difficulty.unwrap_or(GameDifficulty::Medium);
+        let daily = options.daily;
+        let mode = options.mode;
+        let answer = match mode {
+            GameMode::Absurdle => None,
+            _ => options.answer,
+        };
+        let (answer, seed) = match answer {
+            // Fibble needs a seed for the lies even when the answer is given.
+            Some(a) if matches!(mode, GameMode::Fibble { .. }) => {
+                let seed = daily.map(|d| d.seed()).or(options.seed);
+                (a, Some(seed.unwrap_or_else(rand::random)))
             }
+            Some(a) => (a, daily.map(|d| d.seed()).or(options.seed)),
+            None => match daily {
+                Some(daily) => {
+                    let answer = daily
+                        .answer(&dictionary, options.word_length)
+                        .expect("Failed to generate answer.");
+                    (answer, Some(daily.seed()))
+                }
+                None => {
+                    let seed = options.seed.unwrap_or_else(rand::random);
+                    let mut rng = StdRng::seed_from_u64(seed);
+                    let answer = dictionary
+                        .generate_answer(options.word_length, &mut rng)
+                        .expect("Failed to generate answer.");
+                    (answer, Some(seed))
+                }
+            },
+        };
+        let max_attempts = options.max_attempts.unwrap_or(default_attempts(difficulty));
+        let word_length = answer.chars().count();
+        let (answer, candidates) = match mode {
+            GameMode::Absurdle => (None, dictionary.answer[word_length].clone()),
+            _ => (Some(answer), Vec::new()),
         };
-        let max_attempts = max_attempts.unwrap_or(match difficulty {
-            GameDifficulty::Easy => 10,
-            GameDifficulty::Medium => 6,
-            GameDifficulty::Hard => 5,
-        });
-        return Game {
+        Game {
             state: GameState::Innit,
-            word_length: answer.len(),
-            max_attempts: max_attempts,
-            guess_count: 0,
-            answer: answer,
-            dictionary: dictionary,
+            difficulty,
+            word_length,
+            max_attempts,
+            mode,
+            answer,
+            candidates,
+            seed,
+            daily,
+            dictionary,
+            history: Vec::new(),
+            lies: Vec::new(),
+            hints: Vec::new(),
+            loss_reason: None,
+            time_limit: options.time_limit,
+            elapsed: Duration::ZERO,
+            started_at: None,
+        }
+    }
+    pub fn guess(&mut self, input: &str) -> Result<GuessResult, GuessError> {
+        if self.tick() {
+            return Err(GuessError::OutOfTime);
+        }
+        let input = self.check_guess(input)?;
+        let result = match &self.answer {
+            Some(answer) => judge::judge(&input, answer),
+            None => self.dodge(&input),
         };
+        match result {
+            Some(result) => {
+                let won = result.iter().all(|state| *state == LetterState::Matched);
+                let result = match self.mode {
+                    GameMode::Fibble { lies } if !won => self.fib(result, lies),
+                    _ => result,
+                };
+                self.history.push(GuessRecord {
+                    word: input,
+                    result: result.clone(),
+                });
+                if won {
+                    self.finish(GameResult::Won);
+                } else if self.get_attempts() >= self.max_attempts {
+                    self.lose(LossReason::OutOfGuesses);
+                }
+                Ok(result)
+            }
+            None => Err(GuessError::JudgeFailed),
+        }
     }
-    pub fn guess(&mut self, input: &str) -> Result<crate::types::GuessResult, String> {
-        if self.state != GameState::InProgress {
-            return Err("Game is not in progress.".to_string());
+    /// Absurdle: gives the feedback that keeps the most candidates alive and
+    /// drops the rest. Ties go to the feedback that reveals the least. Once a
+    /// single candidate is left it becomes the answer.
+    fn dodge(&mut self, input: &str) -> Option<GuessResult> {
+        let mut groups: HashMap<Pattern, Vec<String>> = HashMap::new();
+        for candidate in &self.candidates {
+            groups
+                .entry(judge::judge_pattern(input, candidate))
+                .or_default()
+                .push(candidate.clone());
+        }
+        let won = judge::all_matched(self.word_length);
+        let (pattern, group) = groups
+            .into_iter()
+            .max_by_key(|(pattern, group)| (group.len(), *pattern != won, Reverse(*pattern)))?;
+        self.candidates = group;
+        if self.candidates.len() == 1 {
+            self.answer = Some(self.candidates[0].clone());
+        }
+        Some(judge::decode(pattern, self.word_length))
+    }
+    /// Fibble: flips `lies` tiles of the row to one of the other two states.
+    /// The rng is seeded per row so replaying a save tells the same lies.
+    fn fib(&mut self, mut result: GuessResult, lies: u8) -> GuessResult {
+        let row = self.history.len() as u64 + 1;
+        let seed = self.seed.unwrap_or_default() ^ row.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut positions = index::sample(&mut rng, result.len(), (lies as usize).min(result.len())).into_vec();
+        positions.sort_unstable();
+        for &i in &positions {
+            // A lie must not turn a miss into an all-green row.
+            let rest_matched = result
+                .iter()
+                .enumerate()
+                .all(|(j, state)| j == i || *state == LetterState::Matched);
+            let others: Vec<LetterState> = [LetterState::Absent, LetterState::Present, LetterState::Matched]
+                .into_iter()
+                .filter(|s| *s != result[i] && !(rest_matched && *s == LetterState::Matched))
+                .collect();
+            result[i] = others[rng.random_range(0..others.len())];
+        }
+        self.lies.push(positions);
+        result
+    }
+    /// Fibble: the positions that lied in each row, revealed once the game
+    /// is over. `None` while the game is running or in other modes.
+    pub fn lies(&self) -> Option<&[Vec<usize>]> {
+        match (self.mode, self.state) {
+            (GameMode::Fibble { .. }, GameState::Over(_)) => Some(&self.lies),
+            _ => None,
+        }
+    }
+    /// The history with honest feedback, for hints. Only Fibble differs from
+    /// `history`.
+    fn true_history(&self) -> Vec<GuessRecord> {
+        match (self.mode, &self.answer) {
+            (GameMode::Fibble { .. }, Some(answer)) => self
+                .history
+                .iter()
+                .filter_map(|r| {
+                    Some(GuessRecord {
+                        word: r.word.clone(),
+                        result: judge::judge(&r.word, answer)?,
+                    })
+                })
+                .collect(),
+            _ => self.history.clone(),
         }
-        let input = input.to_lowercase();
-        if !input.chars().all(|c| c.is_ascii_lowercase()) {
-            return Err("Word can only contain A-Z.".to_string());
+    }
+    /// Runs every check `guess` makes without playing the guess. Returns the
+    /// input folded through the alphabet.
+    pub(crate) fn check_guess(&self, input: &str) -> Result<String, GuessError> {
+        if self.state != GameState::InProgress {
+            return Err(GuessError::NotInProgress);
         }
-        if input.len() != self.word_length {
-            return Err(format!("Input length must be {}.", self.word_length));
+        let input = self
+            .dictionary
+            .alphabet()
+            .fold(input)
+            .map_err(|_| GuessError::InvalidCharacters)?;
+        let length = input.chars().count();
+        if length != self.word_length {
+            return Err(GuessError::WrongLength {
+                expected: self.word_length,
+                got: length,
+            });
         }
         if !self.dictionary.is_valid_word(&input) {
-            return Err("Word not found.".to_string());
+            return Err(GuessError::NotInDictionary);
         }
-        self.guess_count += 1;
-        match crate::game::judge::judge(&input, &self.answer) {
-            Some(result) => {
-                if result.iter().all(|state| *state == LetterState::Matched) {
-                    self.state = GameState::Over(GameResult::Won);
-                } else if self.guess_count >= self.max_attempts {
-                    self.state = GameState::Over(GameResult::Lost);
+        // Lies would make hard mode demand letters the answer doesn't have,
+        // and in Mastermind its errors would give away the hidden tiles.
+        if self.difficulty == GameDifficulty::Hard
+            && !matches!(self.mode, GameMode::Fibble { .. } | GameMode::Mastermind)
+        {
+            self.check_hard_mode(&input)
+                .map_err(GuessError::HardModeViolation)?;
+        }
+        Ok(input)
+    }
+    /// Hard mode: every revealed hint must be used in later guesses.
+    /// Matched letters have to stay in place and present letters have to
+    /// appear at least as many times as they were revealed.
+    fn check_hard_mode(&self, input: &str) -> Result<(), HardModeViolation> {
+        let input_chars: Vec<char> = input.chars().collect();
+        for record in &self.history {
+            let mut required: Vec<(char, usize)> = Vec::new();
+            for (i, (c, state)) in record.word.chars().zip(record.result.iter()).enumerate() {
+                if *state == LetterState::Absent {
+                    continue;
+                }
+                if *state == LetterState::Matched && input_chars[i] != c {
+                    return Err(HardModeViolation::MissingMatched {
+                        position: i,
+                        letter: c,
+                    });
+                }
+                match required.iter_mut().find(|(r, _)| *r == c) {
+                    Some((_, count)) => *count += 1,
+                    None => required.push((c, 1)),
+                }
+            }
+            for (c, count) in required {
+                let found = input_chars.iter().filter(|&&x| x == c).count();
+                if found < count {
+                    return Err(HardModeViolation::MissingPresent { letter: c, count });
                 }
-                return Ok(result);
             }
-            None => return Err("Failed to judge the guess".to_string()),
         }
+        Ok(())
     }
     pub fn get_state(&self) -> GameState {
         self.state
     }
     pub fn get_answer(&self) -> &str {
         match self.state {
-            GameState::Over(_) => &self.answer,
+            GameState::Over(_) => self.answer.as_deref().unwrap_or_default(),
             _ => {
                 panic!("Answer can only be retrieved when the game is over.");
             }
         }
     }
+    /// The answer regardless of state, for saving. `None` while an Absurdle
+    /// host is still undecided.
+    pub(crate) fn answer(&self) -> Option<&str> {
+        self.answer.as_deref()
+    }
+    pub fn get_mode(&self) -> GameMode {
+        self.mode
+    }
+    /// Starts the clock, or restarts it keeping the time banked so far.
     pub fn start(&mut self) {
         self.state = GameState::InProgress;
+        self.started_at = Some(Instant::now());
+    }
+    fn lose(&mut self, reason: LossReason) {
+        self.loss_reason = Some(reason);
+        self.finish(GameResult::Lost);
+    }
+    fn finish(&mut self, result: GameResult) {
+        if self.answer.is_none() {
+            self.answer = self.candidates.first().cloned();
+        }
+        self.state = GameState::Over(result);
+        if let Some(started_at) = self.started_at.take() {
+            self.elapsed += started_at.elapsed();
+        }
+    }
+    /// Time spent playing so far. Stops counting once the game is over.
+    pub fn get_duration(&self) -> Duration {
+        self.elapsed + self.started_at.map_or(Duration::ZERO, |t| t.elapsed())
+    }
+    pub(crate) fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed = elapsed;
+        if self.started_at.is_some() {
+            self.started_at = Some(Instant::now());
+        }
+    }
+    /// The seed the answer was drawn from, or `None` if the answer was given
+    /// explicitly without one (Fibble always has one). Starting a new game with the same seed and difficulty
+    /// replays the same puzzle.
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+    pub fn get_word(&self) -> &Rc<Word> {
+        &self.dictionary
+    }
+    pub fn get_daily(&self) -> Option<Daily> {
+        self.daily
+    }
+    pub fn get_difficulty(&self) -> GameDifficulty {
+        self.difficulty
     }
     pub fn get_attempts(&self) -> u8 {
-        self.guess_count
+        self.history.len() as u8
+    }
+    /// Every accepted guess with its result, oldest first.
+    pub fn history(&self) -> &[GuessRecord] {
+        &self.history
+    }
+    pub fn last_guess(&self) -> Option<&GuessRecord> {
+        self.history.last()
+    }
+    /// Gives a hint and records it. Hints don't use up attempts but count
+    /// towards `hint_cost`.
+    pub fn hint(&mut self, kind: HintKind) -> Result<Hint, HintError> {
+        if self.state != GameState::InProgress {
+            return Err(HintError::NotInProgress);
+        }
+        let history = self.true_history();
+        let hint = match kind {
+            HintKind::RevealLetter => {
+                // An undecided Absurdle host can only give away letters that
+                // every remaining candidate shares.
+                let words: Vec<&str> = match &self.answer {
+                    Some(answer) => vec![answer],
+                    None => self.candidates.iter().map(String::as_str).collect(),
+                };
+                let letter_at = |i: usize| {
+                    let letter = words.first()?.chars().nth(i)?;
+                    words.iter().all(|w| w.chars().nth(i) == Some(letter)).then_some(letter)
+                };
+                // Only tiles the player can trust count as known: Mastermind
+                // shows none and any Fibble tile may be a lie.
+                let shown: &[GuessRecord] = match self.mode {
+                    GameMode::Mastermind | GameMode::Fibble { .. } => &[],
+                    _ => &self.history,
+                };
+                let known = |i: usize| {
+                    shown
+                        .iter()
+                        .any(|r| r.result.get(i) == Some(&LetterState::Matched))
+                        || self.hints.iter().any(|h| {
+                            matches!(h, Hint::RevealLetter { position, .. } if *position == i)
+                        })
+                };
+                let mut unknown = (0..self.word_length).filter(|&i| !known(i)).peekable();
+                if unknown.peek().is_none() {
+                    return Err(HintError::NothingToReveal);
+                }
+                let (position, letter) = unknown
+                    .find_map(|i| letter_at(i).map(|letter| (i, letter)))
+                    .ok_or(HintError::Undecided)?;
+                Hint::RevealLetter { position, letter }
+            }
+            HintKind::CandidatesLeft => Hint::CandidatesLeft(self.solver(&history).candidates().len()),
+            HintKind::SuggestWord => {
+                let solver = self.solver(&history);
+                Hint::SuggestWord(solver.best_candidate().ok_or(HintError::NoCandidates)?)
+            }
+        };
+        self.hints.push(hint.clone());
+        Ok(hint)
+    }
+    /// A solver over the answers that fit what the player has been shown.
+    /// In Mastermind that is only the counts of each row.
+    fn solver(&self, history: &[GuessRecord]) -> Solver<'_> {
+        if self.mode != GameMode::Mastermind {
+            return Solver::from_history(&self.dictionary, self.word_length, history);
+        }
+        let counts = self.counts();
+        let candidates = self.dictionary.answer[self.word_length]
+            .iter()
+            .filter(|answer| {
+                history
+                    .iter()
+                    .zip(&counts)
+                    .all(|(r, count)| judge::count(&r.word, answer).as_ref() == Some(count))
+            })
+            .cloned()
+            .collect();
+        Solver::with_candidates(&self.dictionary, self.word_length, candidates)
+    }
+    /// Mastermind-style counts for every row of `history`.
+    pub fn counts(&self) -> Vec<CountResult> {
+        self.history.iter().map(|r| CountResult::from(&r.result)).collect()
+    }
+    /// Every hint given so far, oldest first.
+    pub fn hints(&self) -> &[Hint] {
+        &self.hints
+    }
+    pub fn hint_cost(&self) -> u32 {
+        self.hints.iter().map(|h| h.kind().cost()).sum()
+    }
+    pub(crate) fn restore_hint(&mut self, hint: Hint) {
+        self.hints.push(hint);
+    }
+    /// The best state revealed so far for every guessed letter. A letter that
+    /// was matched anywhere counts as matched, otherwise present beats absent.
+    /// Empty in Mastermind, where no single letter is revealed.
+    pub fn letter_states(&self) -> HashMap<char, LetterState> {
+        let mut states = HashMap::new();
+        if self.mode == GameMode::Mastermind {
+            return states;
+        }
+        for record in &self.history {
+            for (c, state) in record.word.chars().zip(record.result.iter()) {
+                let best = states.entry(c).or_insert(*state);
+                if rank(*state) > rank(*best) {
+                    *best = *state;
+                }
+            }
+        }
+        states
     }
     pub fn get_max_attempts(&self) -> u8 {
         self.max_attempts
@@ -89,6 +478,287 @@ impl Game {
         self.word_length
     }
     pub fn give_up(&mut self) {
-        self.state = GameState::Over(GameResult::Lost);
+        self.lose(LossReason::GaveUp);
+    }
+    /// Why the game was lost, `None` unless it was.
+    pub fn loss_reason(&self) -> Option<LossReason> {
+        self.loss_reason
+    }
+    pub fn get_time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+    /// Time left before the limit runs out, `None` without a limit.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.time_limit.map(|limit| limit.saturating_sub(self.get_duration()))
+    }
+    /// Ends the game as lost once its time limit has run out. Returns whether
+    /// that happened on this call. UIs should call it regularly; `guess` does
+    /// too.
+    pub fn tick(&mut self) -> bool {
+        if self.state == GameState::InProgress
+            && let Some(limit) = self.time_limit
+            && self.get_duration() >= limit
+        {
+            self.lose(LossReason::OutOfTime);
+            // Noticed late, but the game can't have lasted past its limit.
+            self.elapsed = limit;
+            return true;
+        }
+        false
+    }
+}
+
+/// Guesses allowed for a single board when none are asked for.
+pub(crate) fn default_attempts(difficulty: GameDifficulty) -> u8 {
+    match difficulty {
+        GameDifficulty::Easy => 10,
+        GameDifficulty::Medium => 6,
+        GameDifficulty::Hard => 5,
+    }
+}
+
+pub(crate) fn rank(state: LetterState) -> u8 {
+    match state {
+        LetterState::Absent => 0,
+        LetterState::Present => 1,
+        LetterState::Matched => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn started(mode: GameMode, answer: &str) -> Game {
+        let mut game = Game::with_word(
+            Rc::new(Word::new(&GameDifficulty::Medium).unwrap()),
+            GameOptions {
+                answer: Some(answer.to_string()),
+                seed: Some(7),
+                mode,
+                ..Default::default()
+            },
+        );
+        game.start();
+        game
+    }
+
+    fn hard(answer: &str) -> Game {
+        let mut game = Game::with_word(
+            Rc::new(Word::new(&GameDifficulty::Medium).unwrap()),
+            GameOptions {
+                answer: Some(answer.to_string()),
+                difficulty: Some(GameDifficulty::Hard),
+                ..Default::default()
+            },
+        );
+        game.start();
+        game
+    }
+
+    fn violation(game: &mut Game, guess: &str) -> Option<HardModeViolation> {
+        match game.guess(guess) {
+            Err(GuessError::HardModeViolation(violation)) => Some(violation),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn hard_mode_keeps_green_letters_in_place() {
+        let mut game = hard("crane");
+        game.guess("crate").unwrap();
+        assert_eq!(
+            violation(&mut game, "trace"),
+            Some(HardModeViolation::MissingMatched { position: 0, letter: 'c' })
+        );
+        assert_eq!(game.get_attempts(), 1);
+        assert!(game.guess("crane").is_ok());
+    }
+
+    #[test]
+    fn hard_mode_requires_yellow_letters() {
+        let mut game = hard("crane");
+        game.guess("north").unwrap();
+        assert_eq!(
+            violation(&mut game, "slate"),
+            Some(HardModeViolation::MissingPresent { letter: 'n', count: 1 })
+        );
+        assert!(game.guess("rayon").is_ok());
+    }
+
+    #[test]
+    fn hard_mode_counts_repeated_letters() {
+        // Both e's of "green" are revealed, so one e is not enough.
+        let mut game = hard("eerie");
+        game.guess("green").unwrap();
+        assert_eq!(
+            violation(&mut game, "rebut"),
+            Some(HardModeViolation::MissingPresent { letter: 'e', count: 2 })
+        );
+        assert!(game.guess("there").is_ok());
+    }
+
+    fn absurdle(answers: &[&str]) -> Game {
+        let json = format!("{{\"5\": {:?}}}", answers);
+        let mut game = Game::with_word(
+            Rc::new(Word::from_json(&json, &json).unwrap()),
+            GameOptions {
+                word_length: Some(5),
+                mode: GameMode::Absurdle,
+                ..Default::default()
+            },
+        );
+        game.start();
+        game
+    }
+
+    #[test]
+    fn absurdle_keeps_the_largest_group() {
+        let mut game = absurdle(&["crane", "crate", "grate", "slate", "plumb", "pious"]);
+        // Only "crate" and "grate" share a pattern against "slate".
+        let result = game.guess("slate").unwrap();
+        assert_eq!(result, judge::judge("slate", "crate").unwrap());
+        assert_eq!(game.candidates, ["crate", "grate"]);
+        assert_eq!(game.answer(), None);
+    }
+
+    #[test]
+    fn absurdle_dodges_a_tied_win() {
+        let mut game = absurdle(&["crate", "grate"]);
+        let result = game.guess("crate").unwrap();
+        assert_ne!(result, vec![LetterState::Matched; 5]);
+        assert_eq!(game.get_state(), GameState::InProgress);
+        assert_eq!(game.answer(), Some("grate"));
+    }
+
+    #[test]
+    fn absurdle_is_won_once_settled() {
+        let mut game = absurdle(&["crane", "slate"]);
+        game.guess("crane").unwrap();
+        assert_eq!(game.candidates, ["slate"]);
+        game.guess("slate").unwrap();
+        assert_eq!(game.get_state(), GameState::Over(GameResult::Won));
+        assert_eq!(game.get_answer(), "slate");
+        assert_eq!(game.get_attempts(), 2);
+    }
+
+    fn fibble(words: &Rc<Word>, seed: u64, lies: u8) -> Game {
+        let mut game = Game::with_word(
+            Rc::clone(words),
+            GameOptions {
+                answer: Some(String::from("crane")),
+                seed: Some(seed),
+                mode: GameMode::Fibble { lies },
+                ..Default::default()
+            },
+        );
+        game.start();
+        game
+    }
+
+    #[test]
+    fn fibble_never_shows_a_false_win() {
+        // "crate" has four greens, so a lie on the fifth tile could fake a win.
+        let words = Rc::new(Word::new(&GameDifficulty::Medium).unwrap());
+        for lies in [1, 2] {
+            for seed in 0..300 {
+                let mut game = fibble(&words, seed, lies);
+                let result = game.guess("crate").unwrap();
+                assert!(result.iter().any(|s| *s != LetterState::Matched), "seed {} lies {}", seed, lies);
+            }
+        }
+    }
+
+    #[test]
+    fn fibble_lies_follow_the_seed() {
+        let words = Rc::new(Word::new(&GameDifficulty::Medium).unwrap());
+        let play = |seed| {
+            let mut game = fibble(&words, seed, 2);
+            for guess in ["slate", "pious", "crane"] {
+                game.guess(guess).unwrap();
+            }
+            (game.history().to_vec(), game.lies().unwrap().to_vec())
+        };
+        assert_eq!(play(11), play(11));
+        assert_ne!(play(11), play(12));
+    }
+
+    #[test]
+    fn fibble_lies_are_counted_per_row() {
+        let mut game = fibble(&Rc::new(Word::new(&GameDifficulty::Medium).unwrap()), 3, 2);
+        assert_eq!(game.lies(), None);
+        for guess in ["slate", "pious", "crate", "crane"] {
+            game.guess(guess).unwrap();
+        }
+        let lies = game.lies().unwrap();
+        // The winning row is told straight.
+        assert_eq!(lies.len(), 3);
+        for (record, lied) in game.history().iter().zip(lies) {
+            assert_eq!(lied.len(), 2);
+            let honest = judge::judge(&record.word, "crane").unwrap();
+            let changed: Vec<usize> = (0..5).filter(|&i| honest[i] != record.result[i]).collect();
+            assert_eq!(&changed, lied, "{}", record.word);
+        }
+    }
+
+    fn timed(limit: Duration) -> Game {
+        Game::with_word(
+            Rc::new(Word::new(&GameDifficulty::Medium).unwrap()),
+            GameOptions {
+                answer: Some(String::from("crane")),
+                time_limit: Some(limit),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn clock_only_runs_once_started() {
+        let mut game = timed(Duration::from_millis(1));
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(game.get_duration(), Duration::ZERO);
+        assert!(!game.tick());
+        game.start();
+        assert_eq!(game.get_state(), GameState::InProgress);
+    }
+
+    #[test]
+    fn running_out_of_time_loses() {
+        let mut game = timed(Duration::from_secs(60));
+        game.start();
+        game.set_elapsed(Duration::from_secs(59));
+        assert!(!game.tick());
+        game.guess("slate").unwrap();
+        game.set_elapsed(Duration::from_secs(61));
+        assert_eq!(game.guess("crane"), Err(GuessError::OutOfTime));
+        assert_eq!(game.get_state(), GameState::Over(GameResult::Lost));
+        assert_eq!(game.loss_reason(), Some(LossReason::OutOfTime));
+        assert_eq!(game.get_duration(), Duration::from_secs(60));
+        assert_eq!(game.get_attempts(), 1);
+        assert!(!game.tick());
+    }
+
+    #[test]
+    fn reveal_skips_matched_tiles() {
+        let mut game = started(GameMode::Classic, "crane");
+        game.guess("crate").unwrap();
+        let hint = game.hint(HintKind::RevealLetter).unwrap();
+        assert_eq!(hint, Hint::RevealLetter { position: 3, letter: 'n' });
+        game.guess("crone").unwrap();
+        assert_eq!(game.hint(HintKind::RevealLetter), Err(HintError::NothingToReveal));
+    }
+
+    #[test]
+    fn mastermind_reveal_ignores_hidden_tiles() {
+        let mut game = started(GameMode::Mastermind, "crane");
+        game.guess("crate").unwrap();
+        game.guess("brine").unwrap();
+        // Every tile but the fourth matched, yet the player only saw counts.
+        let hint = game.hint(HintKind::RevealLetter).unwrap();
+        assert_eq!(hint, Hint::RevealLetter { position: 0, letter: 'c' });
+        for _ in 1..5 {
+            game.hint(HintKind::RevealLetter).unwrap();
+        }
+        assert_eq!(game.hint(HintKind::RevealLetter), Err(HintError::NothingToReveal));
     }
 }
@@ -1,31 +1,255 @@
-use crate::types::{GuessResult, LetterState};
-use core::panic;
+use crate::types::{CountResult, GuessResult, LetterState};
+use std::collections::HashMap;
+
+/// Feedback for one guess packed into a base-3 number. Digit `i` (least
+/// significant first) describes letter `i`: 0 absent, 1 present, 2 matched.
+pub type Pattern = u32;
+
+/// Longest word a `Pattern` can describe (3^20 still fits in a `u32`).
+pub const MAX_PATTERN_LEN: usize = 20;
+/// Longest word a `PatternMatrix` holds: 3^10 patterns fit in a `u16`.
+pub const MAX_MATRIX_LEN: usize = 10;
 
 pub fn judge(guess: &str, answer: &str) -> Option<GuessResult> {
-    if guess.len() != answer.len() {
-        panic!("Guess and answer must be of the same length");
+    if fits_pattern(guess, answer) {
+        return Some(decode(pattern(guess.as_bytes(), answer.as_bytes()), guess.len()));
     }
-    let mut result = vec![LetterState::Absent; guess.len()];
+    Some(judge_chars(guess, answer))
+}
+
+/// `judge` letter by letter, for words `pattern` can't take.
+fn judge_chars(guess: &str, answer: &str) -> GuessResult {
     let guess_chars: Vec<char> = guess.chars().collect();
-    let mut answer_chars: Vec<char> = answer.chars().collect();
+    // Letters of the answer not yet matched to a letter of the guess.
+    let mut answer_chars: Vec<Option<char>> = answer.chars().map(Some).collect();
+    if guess_chars.len() != answer_chars.len() {
+        panic!("Guess and answer must be of the same length");
+    }
+    let mut result = vec![LetterState::Absent; guess_chars.len()];
     for i in 0..guess_chars.len() {
-        if guess_chars[i] == answer_chars[i] {
+        if Some(guess_chars[i]) == answer_chars[i] {
             result[i] = LetterState::Matched;
-            answer_chars[i] = '_';
+            answer_chars[i] = None;
         }
     }
     for i in 0..guess_chars.len() {
         if result[i] == LetterState::Matched {
             continue;
         }
-        if answer_chars.contains(&guess_chars[i]) {
+        if let Some(index) = answer_chars.iter().position(|&c| c == Some(guess_chars[i])) {
             result[i] = LetterState::Present;
-            let index = answer_chars
-                .iter()
-                .position(|&c| c == guess_chars[i])
-                .unwrap();
-            answer_chars[index] = '_';
+            answer_chars[index] = None;
+        }
+    }
+    result
+}
+
+/// `judge` as a `Pattern`, without building the result for words `pattern`
+/// can take.
+pub fn judge_pattern(guess: &str, answer: &str) -> Pattern {
+    if fits_pattern(guess, answer) {
+        return pattern(guess.as_bytes(), answer.as_bytes());
+    }
+    judge(guess, answer).map_or(0, |result| encode(&result))
+}
+
+/// Whether `pattern` can judge the words as bytes.
+fn fits_pattern(guess: &str, answer: &str) -> bool {
+    guess.is_ascii() && answer.is_ascii() && guess.len() == answer.len() && guess.len() <= MAX_PATTERN_LEN
+}
+
+/// Mastermind-style feedback for `guess`: the same judgement, reduced to
+/// counts.
+pub fn count(guess: &str, answer: &str) -> Option<CountResult> {
+    judge(guess, answer).map(|result| CountResult::from(&result))
+}
+
+/// Allocation-free judge over bytes. Both slices must have the same length,
+/// at most `MAX_PATTERN_LEN`.
+pub fn pattern(guess: &[u8], answer: &[u8]) -> Pattern {
+    debug_assert_eq!(guess.len(), answer.len());
+    debug_assert!(guess.len() <= MAX_PATTERN_LEN);
+    let mut unmatched = [0u8; 256];
+    let mut matched: u32 = 0;
+    for i in 0..guess.len() {
+        if guess[i] == answer[i] {
+            matched |= 1 << i;
+        } else {
+            unmatched[answer[i] as usize] += 1;
         }
     }
-    return Some(result);
+    let mut code: Pattern = 0;
+    let mut weight: Pattern = 1;
+    for (i, &g) in guess.iter().enumerate() {
+        if matched & (1 << i) != 0 {
+            code += 2 * weight;
+        } else if unmatched[g as usize] > 0 {
+            unmatched[g as usize] -= 1;
+            code += weight;
+        }
+        weight *= 3;
+    }
+    code
+}
+
+pub fn encode(result: &GuessResult) -> Pattern {
+    result.iter().rev().fold(0, |code, state| {
+        code * 3
+            + match state {
+                LetterState::Absent => 0,
+                LetterState::Present => 1,
+                LetterState::Matched => 2,
+            }
+    })
+}
+
+pub fn decode(mut pattern: Pattern, word_length: usize) -> GuessResult {
+    let mut result = Vec::with_capacity(word_length);
+    for _ in 0..word_length {
+        result.push(match pattern % 3 {
+            0 => LetterState::Absent,
+            1 => LetterState::Present,
+            _ => LetterState::Matched,
+        });
+        pattern /= 3;
+    }
+    result
+}
+
+/// The pattern of a correct guess.
+pub fn all_matched(word_length: usize) -> Pattern {
+    3u32.pow(word_length as u32) - 1
+}
+
+/// Every guess judged against every answer once up front, for code that
+/// scores the same pairs over and over. Words must all have one ASCII length
+/// of at most `MAX_MATRIX_LEN`. Patterns are stored answer by answer, so
+/// the patterns of every guess against a few answers are read in order.
+pub struct PatternMatrix {
+    guesses: Vec<String>,
+    answers: Vec<String>,
+    guess_index: HashMap<String, usize>,
+    patterns: Vec<u16>,
+}
+
+impl PatternMatrix {
+    /// `None` if the words don't fit, see `PatternMatrix`.
+    pub fn new(guesses: Vec<String>, answers: Vec<String>) -> Option<Self> {
+        let len = answers.first().or(guesses.first()).map_or(0, String::len);
+        let fits = |w: &String| w.is_ascii() && w.len() == len;
+        if len > MAX_MATRIX_LEN || !guesses.iter().chain(&answers).all(fits) {
+            return None;
+        }
+        let mut patterns = Vec::with_capacity(guesses.len() * answers.len());
+        for answer in &answers {
+            for guess in &guesses {
+                patterns.push(pattern(guess.as_bytes(), answer.as_bytes()) as u16);
+            }
+        }
+        let guess_index = guesses
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (g.clone(), i))
+            .collect();
+        Some(PatternMatrix {
+            guesses,
+            answers,
+            guess_index,
+            patterns,
+        })
+    }
+    pub fn guesses(&self) -> &[String] {
+        &self.guesses
+    }
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+    pub fn guess_index(&self, guess: &str) -> Option<usize> {
+        self.guess_index.get(guess).copied()
+    }
+    /// Pattern of guess number `guess` against answer number `answer`.
+    pub fn get(&self, guess: usize, answer: usize) -> Pattern {
+        self.patterns[answer * self.guesses.len() + guess] as Pattern
+    }
+    /// Patterns of every guess against answer number `answer`, in guess order.
+    pub fn column(&self, answer: usize) -> &[u16] {
+        let n = self.guesses.len();
+        &self.patterns[answer * n..(answer + 1) * n]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LetterState::{Absent as A, Matched as M, Present as P};
+
+    /// Pairs with repeated letters in the guess, the answer or both.
+    const PAIRS: [(&str, &str); 10] = [
+        ("speed", "abide"),
+        ("speed", "erase"),
+        ("eerie", "there"),
+        ("eerie", "speed"),
+        ("llama", "hello"),
+        ("allow", "llama"),
+        ("geese", "eerie"),
+        ("crane", "crane"),
+        ("mamma", "maxim"),
+        ("abbey", "kebab"),
+    ];
+
+    #[test]
+    fn duplicate_letters_are_judged_once_each() {
+        assert_eq!(judge("speed", "abide").unwrap(), vec![A, A, P, A, P]);
+        assert_eq!(judge("speed", "erase").unwrap(), vec![P, A, P, P, A]);
+        assert_eq!(judge("eerie", "there").unwrap(), vec![P, A, P, A, M]);
+        assert_eq!(judge("llama", "hello").unwrap(), vec![P, P, A, A, A]);
+        assert_eq!(judge("mamma", "maxim").unwrap(), vec![M, M, P, A, A]);
+    }
+
+    #[test]
+    fn pattern_agrees_with_judge() {
+        for (guess, answer) in PAIRS {
+            let expected = judge_chars(guess, answer);
+            let code = pattern(guess.as_bytes(), answer.as_bytes());
+            assert_eq!(code, encode(&expected), "{} against {}", guess, answer);
+            assert_eq!(decode(code, guess.len()), expected, "{} against {}", guess, answer);
+            assert_eq!(judge_pattern(guess, answer), code);
+            assert_eq!(judge(guess, answer).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn encode_and_decode_round_trip() {
+        for code in 0..3u32.pow(5) {
+            assert_eq!(encode(&decode(code, 5)), code);
+        }
+        assert_eq!(decode(all_matched(5), 5), vec![M; 5]);
+    }
+
+    #[test]
+    fn matrix_agrees_with_pattern() {
+        let words = |side: fn((&'static str, &'static str)) -> &'static str| -> Vec<String> {
+            let mut words: Vec<String> = PAIRS.iter().map(|&pair| side(pair).to_string()).collect();
+            words.sort();
+            words.dedup();
+            words
+        };
+        let matrix = PatternMatrix::new(words(|pair| pair.0), words(|pair| pair.1)).unwrap();
+        for (a, answer) in matrix.answers().iter().enumerate() {
+            for (g, guess) in matrix.guesses().iter().enumerate() {
+                let expected = encode(&judge_chars(guess, answer));
+                assert_eq!(matrix.get(g, a), expected, "{} against {}", guess, answer);
+                assert_eq!(matrix.column(a)[g] as Pattern, expected);
+            }
+            assert_eq!(matrix.guess_index(&matrix.guesses()[0]), Some(0));
+        }
+    }
+
+    #[test]
+    fn matrix_rejects_words_it_cannot_hold() {
+        let words = |w: &[&str]| w.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        assert!(PatternMatrix::new(words(&["crane"]), words(&["cranes"])).is_none());
+        assert!(PatternMatrix::new(words(&["abcdefghijk"]), words(&["abcdefghijk"])).is_none());
+        assert!(PatternMatrix::new(words(&["größe"]), words(&["größe"])).is_none());
+    }
 }
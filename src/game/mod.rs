@@ -1,2 +1,10 @@
 pub mod judge;
-pub mod game;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod game;
+pub mod daily;
+pub mod save;
+pub mod share;
+pub mod solver;
+pub mod strategy;
+pub mod multi;
+pub mod blitz;
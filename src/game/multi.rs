@@ -0,0 +1,193 @@
+use rand::{SeedableRng, rngs::StdRng};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::{
+    game::game::{self, Game, GameOptions},
+    types::*,
+    word::{alphabet::Alphabet, word::Word},
+};
+
+/// Most boards a multi-board game can have.
+pub const MAX_BOARDS: usize = 16;
+
+/// Several answers of the same length played at once (Dordle, Quordle,
+/// Octordle). Every guess goes to each board that isn't solved yet, and all
+/// boards share one attempt limit.
+pub struct MultiGame {
+    boards: Vec<Game>,
+    seed: Option<u64>,
+    max_attempts: u8,
+    guesses: Vec<String>,
+}
+
+impl MultiGame {
+    /// Draws `count` distinct answers from `options.seed` (or a random seed).
+    /// `options.answer` and `options.daily` are ignored.
+    pub fn with_word(dictionary: Rc<Word>, count: usize, options: GameOptions) -> Result<Self, AnswerError> {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let first = dictionary.generate_answer(options.word_length, &mut rng)?;
+        let length = first.chars().count();
+        if dictionary.answer[length].len() < count {
+            return Err(AnswerError::TooFewWords { length, needed: count });
+        }
+        let mut answers = vec![first];
+        while answers.len() < count {
+            let answer = dictionary.generate_answer(Some(length), &mut rng)?;
+            if !answers.contains(&answer) {
+                answers.push(answer);
+            }
+        }
+        let mut game = Self::with_answers(dictionary, answers, options);
+        game.seed = Some(seed);
+        Ok(game)
+    }
+    /// Plays the given answers, which must all have the same length. Without
+    /// `options.max_attempts` each board past the first adds one guess to
+    /// the difficulty's usual limit.
+    pub fn with_answers(dictionary: Rc<Word>, answers: Vec<String>, options: GameOptions) -> Self {
+        let difficulty = options.difficulty.unwrap_or(GameDifficulty::Medium);
+        let extra = answers.len().saturating_sub(1).min(u8::MAX as usize) as u8;
+        let max_attempts = options
+            .max_attempts
+            .unwrap_or(game::default_attempts(difficulty).saturating_add(extra));
+        let boards = answers
+            .into_iter()
+            .map(|answer| {
+                Game::with_word(
+                    Rc::clone(&dictionary),
+                    GameOptions {
+                        answer: Some(answer),
+                        difficulty: Some(difficulty),
+                        max_attempts: Some(max_attempts),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        MultiGame {
+            boards,
+            seed: options.seed,
+            max_attempts,
+            guesses: Vec::new(),
+        }
+    }
+    pub fn start(&mut self) {
+        for board in &mut self.boards {
+            board.start();
+        }
+    }
+    /// Plays `input` on every unsolved board. The guess is checked against
+    /// all of them first, so a guess one board rejects (e.g. in hard mode)
+    /// isn't played on any. The result has one entry per board, `None` for
+    /// boards that were already solved.
+    pub fn guess(&mut self, input: &str) -> Result<Vec<Option<GuessResult>>, GuessError> {
+        if self.get_state() != GameState::InProgress {
+            return Err(GuessError::NotInProgress);
+        }
+        let mut word = String::new();
+        for board in self.boards.iter().filter(|b| b.get_state() == GameState::InProgress) {
+            word = board.check_guess(input)?;
+        }
+        let mut results = Vec::with_capacity(self.boards.len());
+        for board in &mut self.boards {
+            if board.get_state() == GameState::InProgress {
+                results.push(Some(board.guess(&word)?));
+            } else {
+                results.push(None);
+            }
+        }
+        self.guesses.push(word);
+        Ok(results)
+    }
+    /// Over once every board is over. Won only if every board was solved.
+    pub fn get_state(&self) -> GameState {
+        match self.boards.iter().find(|b| !matches!(b.get_state(), GameState::Over(_))) {
+            Some(board) => board.get_state(),
+            None if self.boards.iter().all(|b| b.get_state() == GameState::Over(GameResult::Won)) => {
+                GameState::Over(GameResult::Won)
+            }
+            None => GameState::Over(GameResult::Lost),
+        }
+    }
+    pub fn give_up(&mut self) {
+        for board in &mut self.boards {
+            if !matches!(board.get_state(), GameState::Over(_)) {
+                board.give_up();
+            }
+        }
+    }
+    pub fn boards(&self) -> &[Game] {
+        &self.boards
+    }
+    pub fn solved(&self) -> usize {
+        self.boards
+            .iter()
+            .filter(|b| b.get_state() == GameState::Over(GameResult::Won))
+            .count()
+    }
+    /// Every guess played so far, oldest first.
+    pub fn guesses(&self) -> &[String] {
+        &self.guesses
+    }
+    pub fn get_attempts(&self) -> u8 {
+        self.guesses.len() as u8
+    }
+    pub fn get_max_attempts(&self) -> u8 {
+        self.max_attempts
+    }
+    pub fn get_word_length(&self) -> usize {
+        self.boards[0].get_word_length()
+    }
+    pub fn get_difficulty(&self) -> GameDifficulty {
+        self.boards[0].get_difficulty()
+    }
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+    /// Boards stop their clocks when solved, so the last one to finish holds
+    /// the time of the whole game.
+    pub fn get_duration(&self) -> Duration {
+        self.boards.iter().map(|b| b.get_duration()).max().unwrap_or(Duration::ZERO)
+    }
+    pub fn alphabet(&self) -> &Alphabet {
+        self.boards[0].get_word().alphabet()
+    }
+    /// The usual name for this many boards.
+    pub fn name(&self) -> String {
+        match self.boards.len() {
+            1 => String::from("Wordle"),
+            2 => String::from("Dordle"),
+            4 => String::from("Quordle"),
+            8 => String::from("Octordle"),
+            n => format!("{}-board Wordle", n),
+        }
+    }
+    /// Letter states merged over the boards that are still being played, or
+    /// over all boards once none are. A letter shows as absent only when it
+    /// is absent everywhere it still matters.
+    pub fn letter_states(&self) -> HashMap<char, LetterState> {
+        let unsolved: Vec<&Game> = self
+            .boards
+            .iter()
+            .filter(|b| !matches!(b.get_state(), GameState::Over(_)))
+            .collect();
+        let boards = if unsolved.is_empty() {
+            self.boards.iter().collect()
+        } else {
+            unsolved
+        };
+        let mut states = HashMap::new();
+        for board in boards {
+            for (c, state) in board.letter_states() {
+                let best = states.entry(c).or_insert(state);
+                if game::rank(state) > game::rank(*best) {
+                    *best = state;
+                }
+            }
+        }
+        states
+    }
+}
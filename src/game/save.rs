@@ -0,0 +1,455 @@
+use serde_json::{Value, json};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::{
+    game::{
+        daily::Daily,
+        game::{Game, GameOptions},
+    },
+    types::*,
+    word::{
+        pack,
+        word::{self, AnswerSource, Word},
+    },
+};
+
+const SAVE_VERSION: u64 = 1;
+/// Keeps the stored answer from being readable at a glance. This is not
+/// encryption, it only makes peeking inconvenient.
+const OBFUSCATION_KEY: u64 = 0x9e37_79b9_7f4a_7c15;
+/// Mixed into the checksum so editing a field means recomputing more than a
+/// plain hash of the file.
+const CHECKSUM_SALT: &str = "wordle-save";
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    NoDataDir,
+    UnsupportedVersion(u64),
+    Tampered,
+    Invalid(String),
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to access save file: {}", err),
+            Self::Json(err) => write!(f, "Save file is not valid JSON: {}", err),
+            Self::NoDataDir => f.write_str("Could not determine where to store the save file."),
+            Self::UnsupportedVersion(v) => write!(f, "Unsupported save file version {}.", v),
+            Self::Tampered => f.write_str("Save file has been modified."),
+            Self::Invalid(msg) => write!(f, "Invalid save file: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(err: std::io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        SaveError::Json(err)
+    }
+}
+
+/// The default save location inside `paths::data_dir`.
+pub fn save_path() -> Option<PathBuf> {
+    crate::paths::data_dir().map(|dir| dir.join("save.json"))
+}
+
+pub fn has_save() -> bool {
+    save_path().is_some_and(|path| path.exists())
+}
+
+/// Writes an in-progress game to the default location.
+pub fn save(game: &Game) -> Result<(), SaveError> {
+    let path = save_path().ok_or(SaveError::NoDataDir)?;
+    save_to(game, &path)
+}
+
+/// Loads the game from the default location. The file is left in place.
+pub fn load() -> Result<Game, SaveError> {
+    let path = save_path().ok_or(SaveError::NoDataDir)?;
+    load_from(&path)
+}
+
+/// Removes the default save file, if there is one.
+pub fn discard() -> Result<(), SaveError> {
+    match save_path() {
+        Some(path) if path.exists() => Ok(fs::remove_file(path)?),
+        _ => Ok(()),
+    }
+}
+
+pub fn save_to(game: &Game, path: &Path) -> Result<(), SaveError> {
+    let mut value = json!({
+        "version": SAVE_VERSION,
+        "difficulty": game.get_difficulty().to_string(),
+        "word_length": game.get_word_length(),
+        "max_attempts": game.get_max_attempts(),
+        "seed": game.get_seed(),
+        "daily": game.get_daily().map(|d| d.number()),
+        // Absurdle feedback depends on the whole history, so only the
+        // guesses are kept and replaying them picks the answer again.
+        "answer": match game.get_mode() {
+            GameMode::Absurdle => None,
+            _ => game.answer().map(obfuscate),
+        },
+        "mode": game.get_mode().to_string(),
+        "answers": game.get_word().source().map(source_to_json),
+        "pack": game.get_word().pack(),
+        "history": game.history().iter().map(|r| r.word.clone()).collect::<Vec<_>>(),
+        "state": state_name(game.get_state()),
+        "elapsed_ms": game.get_duration().as_millis() as u64,
+        "time_limit_ms": game.get_time_limit().map(|limit| limit.as_millis() as u64),
+        "hints": game.hints().iter().map(hint_to_json).collect::<Vec<_>>(),
+    });
+    let checksum = checksum(&value);
+    value["checksum"] = Value::String(format!("{:016x}", checksum));
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&value)?)?;
+    Ok(())
+}
+
+/// Rebuilds a game by replaying the saved guesses against the saved answer,
+/// so the board and state are derived rather than trusted.
+pub fn load_from(path: &Path) -> Result<Game, SaveError> {
+    let mut value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let map = value
+        .as_object_mut()
+        .ok_or_else(|| SaveError::Invalid("expected an object".to_string()))?;
+
+    let version = map.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version != SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+    let stored_checksum = match map.remove("checksum") {
+        Some(Value::String(s)) => u64::from_str_radix(&s, 16).map_err(|_| SaveError::Tampered)?,
+        _ => return Err(SaveError::Tampered),
+    };
+    if stored_checksum != checksum(&value) {
+        return Err(SaveError::Tampered);
+    }
+
+    let field = |name: &'static str| {
+        value
+            .get(name)
+            .ok_or_else(|| SaveError::Invalid(format!("missing field '{}'", name)))
+    };
+    let difficulty: GameDifficulty = field("difficulty")?
+        .as_str()
+        .unwrap_or_default()
+        .parse()
+        .map_err(SaveError::Invalid)?;
+    let word_length = field("word_length")?.as_u64().unwrap_or(0) as usize;
+    let max_attempts = field("max_attempts")?
+        .as_u64()
+        .and_then(|n| u8::try_from(n).ok())
+        .ok_or_else(|| SaveError::Invalid("bad max_attempts".to_string()))?;
+    let seed = field("seed")?.as_u64();
+    let daily = match field("daily")?.as_u64() {
+        Some(n) => Some(
+            Daily::from_number(n as u32).map_err(|e| SaveError::Invalid(e.to_string()))?,
+        ),
+        None => None,
+    };
+    let mode: GameMode = match value.get("mode").and_then(Value::as_str) {
+        Some(mode) => mode.parse().map_err(SaveError::Invalid)?,
+        None => GameMode::Classic,
+    };
+    let answer = match mode {
+        GameMode::Absurdle => None,
+        _ => Some(
+            field("answer")?
+                .as_str()
+                .and_then(deobfuscate)
+                .ok_or_else(|| SaveError::Invalid("bad answer".to_string()))?,
+        ),
+    };
+    if answer.as_ref().is_some_and(|a| a.chars().count() != word_length) {
+        return Err(SaveError::Invalid("answer length mismatch".to_string()));
+    }
+    let history: Vec<String> = field("history")?
+        .as_array()
+        .map(|arr| arr.iter().filter_map(|w| w.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    let state = field("state")?.as_str().unwrap_or_default().to_string();
+    let elapsed = value.get("elapsed_ms").and_then(Value::as_u64).unwrap_or(0);
+    let time_limit = value.get("time_limit_ms").and_then(Value::as_u64).map(Duration::from_millis);
+    let hints: Vec<Hint> = match value.get("hints").and_then(Value::as_array) {
+        Some(arr) => arr
+            .iter()
+            .map(hint_from_json)
+            .collect::<Option<_>>()
+            .ok_or_else(|| SaveError::Invalid("bad hint".to_string()))?,
+        None => Vec::new(),
+    };
+
+    // Replayed against the answer list the game was started with. Saves
+    // without one are from before it was kept and used the tier's list.
+    let source = match value.get("answers").filter(|v| !v.is_null()) {
+        Some(source) => source_from_json(source).ok_or_else(|| SaveError::Invalid("bad answers".to_string()))?,
+        None => {
+            // Daily puzzles were drawn from the Medium tier back then.
+            let tier = if daily.is_some() { GameDifficulty::Medium } else { difficulty };
+            AnswerSource::List(word::answer_list(&tier).to_string())
+        }
+    };
+    let words = Word::from_source(&source).map_err(|err| SaveError::Invalid(err.to_string()))?;
+    // Pack games are replayed against the pack as it is installed now.
+    let words = match value.get("pack").and_then(Value::as_str) {
+        Some(id) => {
+            let pack = pack::find(id).map_err(|err| SaveError::Invalid(err.to_string()))?;
+            words.with_pack(&pack)
+        }
+        None => words,
+    };
+    let mut game = Game::with_word(
+        Rc::new(words),
+        GameOptions {
+            answer,
+            difficulty: Some(difficulty),
+            max_attempts: Some(max_attempts),
+            word_length: Some(word_length),
+            seed,
+            daily,
+            time_limit,
+            mode,
+        },
+    );
+    game.start();
+    for word in &history {
+        game.guess(word)
+            .map_err(|err| SaveError::Invalid(format!("saved guess '{}': {}", word, err)))?;
+    }
+    if state_name(game.get_state()) != state {
+        return Err(SaveError::Invalid("state does not match history".to_string()));
+    }
+    game.set_elapsed(Duration::from_millis(elapsed));
+    for hint in hints {
+        game.restore_hint(hint);
+    }
+    Ok(game)
+}
+
+fn source_to_json(source: &AnswerSource) -> Value {
+    match source {
+        AnswerSource::List(name) => json!({ "list": name }),
+        AnswerSource::File(path) => json!({ "file": path }),
+    }
+}
+
+fn source_from_json(value: &Value) -> Option<AnswerSource> {
+    if let Some(name) = value.get("list") {
+        return Some(AnswerSource::List(name.as_str()?.to_string()));
+    }
+    Some(AnswerSource::File(value.get("file")?.as_str()?.to_string()))
+}
+
+fn hint_to_json(hint: &Hint) -> Value {
+    match hint {
+        Hint::RevealLetter { position, letter } => {
+            json!({ "kind": "reveal", "position": position, "letter": letter.to_string() })
+        }
+        Hint::CandidatesLeft(n) => json!({ "kind": "count", "value": n }),
+        Hint::SuggestWord(word) => json!({ "kind": "suggest", "word": word }),
+    }
+}
+
+fn hint_from_json(value: &Value) -> Option<Hint> {
+    match value.get("kind")?.as_str()? {
+        "reveal" => Some(Hint::RevealLetter {
+            position: value.get("position")?.as_u64()? as usize,
+            letter: value.get("letter")?.as_str()?.chars().next()?,
+        }),
+        "count" => Some(Hint::CandidatesLeft(value.get("value")?.as_u64()? as usize)),
+        "suggest" => Some(Hint::SuggestWord(value.get("word")?.as_str()?.to_string())),
+        _ => None,
+    }
+}
+
+fn state_name(state: GameState) -> &'static str {
+    match state {
+        GameState::Innit => "init",
+        GameState::InProgress => "in_progress",
+        GameState::Over(GameResult::Won) => "won",
+        GameState::Over(GameResult::Lost) => "lost",
+    }
+}
+
+/// 64-bit FNV-1a over the salted compact JSON. serde_json keeps object keys
+/// sorted, so the serialisation is stable.
+fn checksum(value: &Value) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in CHECKSUM_SALT.bytes().chain(value.to_string().bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+fn keystream() -> impl Iterator<Item = u8> {
+    let mut state = OBFUSCATION_KEY;
+    std::iter::repeat_with(move || {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as u8
+    })
+}
+
+fn obfuscate(answer: &str) -> String {
+    answer
+        .bytes()
+        .zip(keystream())
+        .map(|(b, k)| format!("{:02x}", b ^ k))
+        .collect()
+}
+
+fn deobfuscate(hex: &str) -> Option<String> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    let bytes: Option<Vec<u8>> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect();
+    let bytes: Vec<u8> = bytes?.into_iter().zip(keystream()).map(|(b, k)| b ^ k).collect();
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A file under the temp dir, unique to this test run.
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("wordle-save-{}-{}", std::process::id(), name))
+    }
+
+    /// A game on a custom answer file, one guess in.
+    fn started_game(answers: &Path) -> Game {
+        fs::write(answers, r#"{"5": ["crane", "slate", "pious"]}"#).unwrap();
+        let source = AnswerSource::File(answers.to_string_lossy().to_string());
+        let words = Word::from_source(&source).unwrap();
+        let mut game = Game::with_word(
+            Rc::new(words),
+            GameOptions {
+                answer: Some(String::from("crane")),
+                difficulty: Some(GameDifficulty::Hard),
+                max_attempts: Some(7),
+                seed: Some(42),
+                ..Default::default()
+            },
+        );
+        game.start();
+        game.guess("slate").unwrap();
+        game
+    }
+
+    #[test]
+    fn save_round_trips() {
+        let (answers, path) = (temp_path("answers.json"), temp_path("round-trip.json"));
+        let game = started_game(&answers);
+        save_to(&game, &path).unwrap();
+        let loaded = load_from(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&answers);
+
+        assert_eq!(loaded.answer(), Some("crane"));
+        assert_eq!(loaded.history(), game.history());
+        assert_eq!(loaded.get_state(), GameState::InProgress);
+        assert_eq!(loaded.get_difficulty(), GameDifficulty::Hard);
+        assert_eq!(loaded.get_max_attempts(), 7);
+        assert_eq!(loaded.get_seed(), Some(42));
+        // Resumed on the same answer list, not the difficulty's.
+        assert_eq!(loaded.get_word().source(), game.get_word().source());
+        assert_eq!(loaded.get_word().answer[5], ["crane", "slate", "pious"]);
+    }
+
+    #[test]
+    fn edited_save_is_rejected() {
+        let (answers, path) = (temp_path("answers-tampered.json"), temp_path("tampered.json"));
+        save_to(&started_game(&answers), &path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        fs::write(&path, text.replace("\"slate\"", "\"crane\"")).unwrap();
+        let result = load_from(&path);
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&answers);
+
+        assert!(matches!(result, Err(SaveError::Tampered)));
+    }
+
+    #[test]
+    fn time_limit_survives_resuming() {
+        let path = temp_path("timed.json");
+        let mut game = Game::with_word(
+            Rc::new(Word::new(&GameDifficulty::Medium).unwrap()),
+            GameOptions {
+                answer: Some(String::from("crane")),
+                time_limit: Some(Duration::from_secs(90)),
+                ..Default::default()
+            },
+        );
+        game.start();
+        game.guess("slate").unwrap();
+        game.set_elapsed(Duration::from_secs(80));
+        save_to(&game, &path).unwrap();
+        let mut loaded = load_from(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.get_time_limit(), Some(Duration::from_secs(90)));
+        assert!(loaded.get_duration() >= Duration::from_secs(80));
+        assert!(loaded.get_duration() < Duration::from_secs(81));
+        loaded.set_elapsed(Duration::from_secs(91));
+        assert!(loaded.tick());
+        assert_eq!(loaded.loss_reason(), Some(LossReason::OutOfTime));
+    }
+
+    #[test]
+    fn fibble_save_replays_the_same_lies() {
+        let path = temp_path("fibble.json");
+        let mut game = Game::with_word(
+            Rc::new(Word::new(&GameDifficulty::Medium).unwrap()),
+            GameOptions {
+                answer: Some(String::from("crane")),
+                seed: Some(5),
+                mode: GameMode::Fibble { lies: 2 },
+                ..Default::default()
+            },
+        );
+        game.start();
+        game.guess("slate").unwrap();
+        game.guess("pious").unwrap();
+        save_to(&game, &path).unwrap();
+        let mut loaded = load_from(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.history(), game.history());
+        game.guess("crane").unwrap();
+        loaded.guess("crane").unwrap();
+        assert!(game.lies().is_some());
+        assert_eq!(loaded.lies(), game.lies());
+    }
+
+    #[test]
+    fn answers_are_obfuscated_reversibly() {
+        let hidden = obfuscate("größe");
+        assert!(!hidden.contains("gr"));
+        assert_eq!(deobfuscate(&hidden).as_deref(), Some("größe"));
+        assert_eq!(deobfuscate("abc"), None);
+    }
+}
@@ -0,0 +1,179 @@
+use std::str::FromStr;
+
+use crate::{game::game::Game, types::*};
+
+/// Which squares the share grid is drawn with.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ShareStyle {
+    #[default]
+    Emoji,
+    /// Orange and blue instead of green and yellow.
+    ColourBlind,
+    /// Plain characters for places that mangle emoji.
+    Ascii,
+}
+
+impl ShareStyle {
+    fn square(self, state: LetterState) -> &'static str {
+        match (self, state) {
+            (Self::Emoji, LetterState::Matched) => "🟩",
+            (Self::Emoji, LetterState::Present) => "🟨",
+            (Self::ColourBlind, LetterState::Matched) => "🟧",
+            (Self::ColourBlind, LetterState::Present) => "🟦",
+            (Self::Emoji | Self::ColourBlind, LetterState::Absent) => "⬛",
+            (Self::Ascii, LetterState::Matched) => "#",
+            (Self::Ascii, LetterState::Present) => "+",
+            (Self::Ascii, LetterState::Absent) => ".",
+        }
+    }
+}
+
+impl FromStr for ShareStyle {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "emoji" => Ok(Self::Emoji),
+            "colourblind" | "colorblind" | "highcontrast" => Ok(Self::ColourBlind),
+            "ascii" => Ok(Self::Ascii),
+            _ => Err(format!("Unknown share style: {}", s)),
+        }
+    }
+}
+
+/// The spoiler-free result, e.g. `Wordle 123 4/6*` followed by one row of
+/// squares per guess. Returns `None` until the game is over.
+pub fn share_text(game: &Game, style: ShareStyle) -> Option<String> {
+    let result = match game.get_state() {
+        GameState::Over(result) => result,
+        _ => return None,
+    };
+    let mut text = String::from(game.get_mode().name());
+    if let Some(daily) = game.get_daily() {
+        text.push_str(&format!(" {}", daily.number()));
+    } else if let Some(seed) = game.get_seed() {
+        text.push_str(&format!(" seed {}", seed));
+    }
+    let score = match result {
+        GameResult::Won => game.get_attempts().to_string(),
+        GameResult::Lost => String::from("X"),
+    };
+    text.push_str(&format!(" {}/{}", score, game.get_max_attempts()));
+    if game.get_difficulty() == GameDifficulty::Hard {
+        text.push('*');
+    }
+    match game.hints().len() {
+        0 => {}
+        1 => text.push_str(" (1 hint)"),
+        n => text.push_str(&format!(" ({} hints)", n)),
+    }
+    text.push('\n');
+    for record in game.history() {
+        text.push('\n');
+        let mut states = record.result.clone();
+        // Mastermind never showed positions, so neither does the grid.
+        if game.get_mode() == GameMode::Mastermind {
+            states.sort_by_key(|state| match state {
+                LetterState::Matched => 0,
+                LetterState::Present => 1,
+                LetterState::Absent => 2,
+            });
+        }
+        for state in &states {
+            text.push_str(style.square(*state));
+        }
+    }
+    Some(text)
+}
+
+/// Wraps `text` in an OSC 52 escape sequence, which asks the terminal to put
+/// it on the system clipboard.
+pub fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::game::GameOptions, word::word::Word};
+    use std::rc::Rc;
+
+    fn played(difficulty: GameDifficulty, guesses: &[&str]) -> Game {
+        let mut game = Game::with_word(
+            Rc::new(Word::new(&GameDifficulty::Medium).unwrap()),
+            GameOptions {
+                answer: Some(String::from("crane")),
+                difficulty: Some(difficulty),
+                max_attempts: Some(6),
+                seed: Some(7),
+                ..Default::default()
+            },
+        );
+        game.start();
+        for guess in guesses {
+            game.guess(guess).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn base64_matches_rfc_4648() {
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (input, output) in vectors {
+            assert_eq!(base64(input.as_bytes()), output, "{:?}", input);
+        }
+        assert_eq!(osc52("foo"), "\x1b]52;c;Zm9v\x07");
+    }
+
+    #[test]
+    fn won_hard_game_shares_its_grid() {
+        let game = played(GameDifficulty::Hard, &["slate", "crate", "crane"]);
+        assert_eq!(
+            share_text(&game, ShareStyle::Emoji).unwrap(),
+            "Wordle seed 7 3/6*\n\n⬛⬛🟩⬛🟩\n🟩🟩🟩⬛🟩\n🟩🟩🟩🟩🟩"
+        );
+        assert_eq!(
+            share_text(&game, ShareStyle::Ascii).unwrap(),
+            "Wordle seed 7 3/6*\n\n..#.#\n###.#\n#####"
+        );
+    }
+
+    #[test]
+    fn lost_game_scores_an_x() {
+        let game = played(GameDifficulty::Medium, &["pious", "dumpy", "nerve", "north", "blimp", "fjord"]);
+        assert_eq!(
+            share_text(&game, ShareStyle::Emoji).unwrap(),
+            "Wordle seed 7 X/6\n\n⬛⬛⬛⬛⬛\n⬛⬛⬛⬛⬛\n🟨⬛🟨⬛🟩\n🟨⬛🟨⬛⬛\n⬛⬛⬛⬛⬛\n⬛⬛⬛🟨⬛"
+        );
+    }
+
+    #[test]
+    fn unfinished_game_has_nothing_to_share() {
+        assert_eq!(share_text(&played(GameDifficulty::Medium, &["slate"]), ShareStyle::Emoji), None);
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::{
+    game::judge::{self, Pattern, PatternMatrix},
+    types::*,
+    word::{
+        query::{WordQuery, WordSource},
+        word::Word,
+    },
+};
+
+/// Above this many candidates only candidates are scored as guesses; scoring
+/// the whole dictionary against a large candidate set is too slow to be
+/// worth the small gain in information.
+const FULL_POOL_THRESHOLD: usize = 300;
+/// Up to this length bucket counts may live in a flat array indexed by
+/// pattern (3^10 entries); longer words always sort their patterns instead.
+const DENSE_BUCKET_MAX_LEN: usize = 10;
+
+/// A possible next guess and how much it is expected to narrow things down.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RankedGuess {
+    pub word: String,
+    /// Expected information gain in bits.
+    pub entropy: f64,
+    /// Whether the guess could itself be the answer.
+    pub is_candidate: bool,
+}
+
+impl fmt::Display for RankedGuess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:.2} bits)", self.word, self.entropy)?;
+        if self.is_candidate {
+            f.write_str(" *")?;
+        }
+        Ok(())
+    }
+}
+
+/// Tracks which answers are still possible and suggests guesses by expected
+/// information gain.
+pub struct Solver<'a> {
+    word: &'a Word,
+    word_length: usize,
+    candidates: Vec<String>,
+    /// Patterns to look up rather than judge. Every candidate is one of its
+    /// answers, and `indices` holds their positions there.
+    matrix: Option<&'a PatternMatrix>,
+    indices: Vec<usize>,
+    /// With a matrix and few candidates, the patterns of every guess against
+    /// the candidates, guess by guess. Empty otherwise.
+    gathered: Vec<u16>,
+}
+
+impl<'a> Solver<'a> {
+    /// Starts with every answer of `word_length` as a candidate.
+    pub fn new(word: &'a Word, word_length: usize) -> Self {
+        let candidates = word.answer.get(word_length).cloned().unwrap_or_default();
+        Self::with_candidates(word, word_length, candidates)
+    }
+    /// Starts from an already narrowed down candidate list.
+    pub fn with_candidates(word: &'a Word, word_length: usize, candidates: Vec<String>) -> Self {
+        Solver {
+            word,
+            word_length,
+            candidates,
+            matrix: None,
+            indices: Vec::new(),
+            gathered: Vec::new(),
+        }
+    }
+    /// Starts with every answer of `matrix` as a candidate, and looks up the
+    /// patterns of its guesses instead of judging them.
+    pub fn with_matrix(word: &'a Word, word_length: usize, matrix: &'a PatternMatrix) -> Self {
+        let mut solver = Solver {
+            word,
+            word_length,
+            candidates: matrix.answers().to_vec(),
+            matrix: Some(matrix),
+            indices: (0..matrix.answers().len()).collect(),
+            gathered: Vec::new(),
+        };
+        solver.gather();
+        solver
+    }
+    pub fn from_history(word: &'a Word, word_length: usize, history: &[GuessRecord]) -> Self {
+        let query = WordQuery::from_history(word_length, history);
+        Self::with_candidates(word, word_length, word.query(&query, WordSource::Answers))
+    }
+    /// Keeps only the candidates that would have produced `result` for `guess`.
+    pub fn apply(&mut self, guess: &str, result: &GuessResult) {
+        let expected = judge::encode(result);
+        let mut keep = Vec::with_capacity(self.candidates.len());
+        self.for_each_pattern(guess, |pattern| keep.push(pattern == expected));
+        let mut kept = keep.iter();
+        self.candidates.retain(|_| *kept.next().unwrap());
+        let mut kept = keep.iter();
+        self.indices.retain(|_| *kept.next().unwrap());
+        self.gather();
+    }
+    /// Fills `gathered` once there are few enough candidates for every
+    /// guess to be scored against them. Reading the matrix a candidate at a
+    /// time is much faster than picking the candidates out guess by guess.
+    fn gather(&mut self) {
+        self.gathered.clear();
+        let n = self.indices.len();
+        let Some(matrix) = self.matrix.filter(|_| n > 0 && n <= FULL_POOL_THRESHOLD) else {
+            return;
+        };
+        self.gathered.resize(matrix.guesses().len() * n, 0);
+        for (k, &answer) in self.indices.iter().enumerate() {
+            for (guess, &pattern) in matrix.column(answer).iter().enumerate() {
+                self.gathered[guess * n + k] = pattern;
+            }
+        }
+    }
+    /// Calls `f` with the pattern `guess` gets against each candidate, in
+    /// candidate order.
+    fn for_each_pattern(&self, guess: &str, mut f: impl FnMut(Pattern)) {
+        let n = self.indices.len();
+        match self.matrix.and_then(|matrix| Some((matrix, matrix.guess_index(guess)?))) {
+            Some((_, g)) if !self.gathered.is_empty() => {
+                self.gathered[g * n..(g + 1) * n].iter().for_each(|&p| f(p as Pattern))
+            }
+            Some((matrix, g)) => self.indices.iter().for_each(|&answer| f(matrix.get(g, answer))),
+            None => self
+                .candidates
+                .iter()
+                .for_each(|candidate| f(judge::judge_pattern(guess, candidate))),
+        }
+    }
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+    pub fn word_length(&self) -> usize {
+        self.word_length
+    }
+    /// How the candidates split up by the feedback `guess` would get. Returns
+    /// the size of every non-empty group, in no particular order.
+    pub fn buckets(&self, guess: &str) -> Vec<usize> {
+        let patterns = judge::all_matched(self.word_length) as usize + 1;
+        if self.word_length <= DENSE_BUCKET_MAX_LEN && self.candidates.len() * 8 >= patterns {
+            let mut counts = vec![0usize; patterns];
+            self.for_each_pattern(guess, |pattern| counts[pattern as usize] += 1);
+            counts.retain(|&n| n > 0);
+            return counts;
+        }
+        // Few candidates for the number of possible patterns: sorting them is
+        // cheaper than clearing a mostly empty table.
+        let mut seen: Vec<Pattern> = Vec::with_capacity(self.candidates.len());
+        self.for_each_pattern(guess, |pattern| seen.push(pattern));
+        seen.sort_unstable();
+        seen.chunk_by(|a, b| a == b).map(|run| run.len()).collect()
+    }
+    /// Expected information gain of `guess` over the current candidates.
+    pub fn entropy(&self, guess: &str) -> f64 {
+        let total = self.candidates.len() as f64;
+        self.buckets(guess)
+            .into_iter()
+            .map(|n| {
+                let p = n as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+    /// The `limit` best guesses, best first. Ties prefer guesses that could
+    /// be the answer.
+    pub fn rank_guesses(&self, limit: usize) -> Vec<RankedGuess> {
+        let mut ranked: Vec<RankedGuess> = self
+            .guess_pool()
+            .into_iter()
+            .map(|(word, is_candidate)| RankedGuess {
+                word: word.to_string(),
+                entropy: self.entropy(word),
+                is_candidate,
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.entropy
+                .total_cmp(&a.entropy)
+                .then(b.is_candidate.cmp(&a.is_candidate))
+                .then(a.word.cmp(&b.word))
+        });
+        ranked.truncate(limit);
+        ranked
+    }
+    /// The words worth scoring as the next guess, each with whether it is
+    /// still a candidate. Candidates come first.
+    pub fn guess_pool(&self) -> Vec<(&str, bool)> {
+        let mut pool: Vec<(&str, bool)> = self.candidates.iter().map(|w| (w.as_str(), true)).collect();
+        if self.candidates.len() <= 2 || self.candidates.len() > FULL_POOL_THRESHOLD {
+            return pool;
+        }
+        let candidate_set: HashSet<&str> = self.candidates.iter().map(String::as_str).collect();
+        let others: Box<dyn Iterator<Item = &str>> = match self.matrix {
+            Some(matrix) => Box::new(matrix.guesses().iter().map(String::as_str)),
+            None => Box::new(
+                self.word
+                    .dictionary
+                    .iter()
+                    .filter(|w| w.chars().count() == self.word_length),
+            ),
+        };
+        pool.extend(others.filter(|w| !candidate_set.contains(w)).map(|w| (w, false)));
+        pool
+    }
+    pub fn best_guess(&self) -> Option<String> {
+        self.rank_guesses(1).pop().map(|g| g.word)
+    }
+    /// The most informative word that could still be the answer.
+    pub fn best_candidate(&self) -> Option<String> {
+        self.candidates
+            .iter()
+            .map(|word| (self.entropy(word), word))
+            .max_by(|a, b| a.0.total_cmp(&b.0).then(b.1.cmp(a.1)))
+            .map(|(_, word)| word.clone())
+    }
+}
+
+/// Parses feedback typed by a player, one character per letter: `g`/`2` for
+/// matched, `y`/`1` for present and `b`/`.`/`-`/`0` for absent.
+pub fn parse_feedback(feedback: &str, word_length: usize) -> Result<GuessResult, String> {
+    let result: GuessResult = feedback
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            'g' | '2' => Ok(LetterState::Matched),
+            'y' | '1' => Ok(LetterState::Present),
+            'b' | 'x' | '.' | '-' | '0' => Ok(LetterState::Absent),
+            _ => Err(format!("Unknown feedback character '{}'.", c)),
+        })
+        .collect::<Result<_, _>>()?;
+    if result.len() != word_length {
+        return Err(format!("Feedback must have {} characters.", word_length));
+    }
+    Ok(result)
+}
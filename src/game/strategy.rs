@@ -0,0 +1,259 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+use crate::{
+    game::{
+        game::{Game, GameOptions},
+        judge::PatternMatrix,
+        solver::Solver,
+    },
+    types::*,
+    word::word::Word,
+};
+
+/// Default memory `benchmark` may spend on a `PatternMatrix`, in MiB. Five
+/// letter words need about 80 MiB; lists too big for the limit are judged
+/// pair by pair instead, which is slower but gives the same results.
+pub const DEFAULT_MATRIX_MIB: usize = 32;
+
+/// Picks guesses for a solver-driven player.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+    /// The next guess for the current candidates, or `None` if there are none.
+    fn choose(&mut self, solver: &Solver) -> Option<String>;
+    /// Whether `choose` always returns the same word for the same candidates.
+    /// Deterministic strategies let callers cache the opening guess.
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+/// Scores every guess in the pool by how the candidates split up and returns
+/// the lowest score. Ties go to candidates, then alphabetical order.
+fn best_by_score(solver: &Solver, score: impl Fn(&[usize], usize) -> f64) -> Option<String> {
+    let total = solver.candidates().len();
+    if total <= 2 {
+        return solver.candidates().first().cloned();
+    }
+    solver
+        .guess_pool()
+        .into_iter()
+        .map(|(word, is_candidate)| (score(&solver.buckets(word), total), !is_candidate, word))
+        .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(b.2)))
+        .map(|(_, _, word)| word.to_string())
+}
+
+/// Maximises expected information gain.
+pub struct EntropyStrategy;
+
+impl Strategy for EntropyStrategy {
+    fn name(&self) -> &'static str {
+        "entropy"
+    }
+    fn choose(&mut self, solver: &Solver) -> Option<String> {
+        solver.best_guess()
+    }
+}
+
+/// Minimises the largest group of candidates that could be left.
+pub struct MinimaxStrategy;
+
+impl Strategy for MinimaxStrategy {
+    fn name(&self) -> &'static str {
+        "minimax"
+    }
+    fn choose(&mut self, solver: &Solver) -> Option<String> {
+        best_by_score(solver, |buckets, _| buckets.iter().copied().max().unwrap_or(0) as f64)
+    }
+}
+
+/// Minimises the expected number of candidates left after the guess.
+pub struct ExpectedSizeStrategy;
+
+impl Strategy for ExpectedSizeStrategy {
+    fn name(&self) -> &'static str {
+        "expected"
+    }
+    fn choose(&mut self, solver: &Solver) -> Option<String> {
+        best_by_score(solver, |buckets, total| {
+            buckets.iter().map(|&n| (n * n) as f64).sum::<f64>() / total as f64
+        })
+    }
+}
+
+/// Guesses a random word that could still be the answer.
+pub struct RandomConsistentStrategy {
+    rng: StdRng,
+}
+
+impl RandomConsistentStrategy {
+    pub fn new(seed: u64) -> Self {
+        RandomConsistentStrategy {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomConsistentStrategy {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+    fn choose(&mut self, solver: &Solver) -> Option<String> {
+        let candidates = solver.candidates();
+        if candidates.is_empty() {
+            return None;
+        }
+        Some(candidates[self.rng.random_range(0..candidates.len())].clone())
+    }
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+pub const STRATEGY_NAMES: [&str; 4] = ["entropy", "minimax", "expected", "random"];
+
+/// Builds a strategy by name. `seed` is used by the random strategy.
+pub fn strategy_by_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    match name {
+        "entropy" => Some(Box::new(EntropyStrategy)),
+        "minimax" => Some(Box::new(MinimaxStrategy)),
+        "expected" => Some(Box::new(ExpectedSizeStrategy)),
+        "random" => Some(Box::new(RandomConsistentStrategy::new(seed))),
+        _ => None,
+    }
+}
+
+/// Plays `game` to the end with `strategy`, starting with `opening` if given.
+/// The game must already be started. `matrix`, if given, must have the
+/// game's answers as its answers.
+pub fn play(
+    game: &mut Game,
+    strategy: &mut dyn Strategy,
+    opening: Option<&str>,
+    matrix: Option<&PatternMatrix>,
+) -> GameResult {
+    let word = Rc::clone(game.get_word());
+    let mut solver = match matrix {
+        Some(matrix) => {
+            let mut solver = Solver::with_matrix(&word, game.get_word_length(), matrix);
+            for record in game.history() {
+                solver.apply(&record.word, &record.result);
+            }
+            solver
+        }
+        None => Solver::from_history(&word, game.get_word_length(), game.history()),
+    };
+    while game.get_state() == GameState::InProgress {
+        let guess = match opening.filter(|_| game.get_attempts() == 0) {
+            Some(opening) => opening.to_string(),
+            None => match strategy.choose(&solver) {
+                Some(guess) => guess,
+                None => {
+                    game.give_up();
+                    break;
+                }
+            },
+        };
+        match game.guess(&guess) {
+            Ok(result) => solver.apply(&guess, &result),
+            Err(_) => {
+                game.give_up();
+                break;
+            }
+        }
+    }
+    match game.get_state() {
+        GameState::Over(result) => result,
+        _ => GameResult::Lost,
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BenchmarkReport {
+    pub strategy: String,
+    pub games: usize,
+    /// Guesses over solved games only.
+    pub total_guesses: usize,
+    pub failures: Vec<String>,
+    /// `(answer, guesses)` for the hardest answers, hardest first. Failed
+    /// games count as `max_attempts + 1`.
+    pub worst: Vec<(String, u8)>,
+}
+
+impl BenchmarkReport {
+    pub fn average_guesses(&self) -> f64 {
+        let solved = self.games - self.failures.len();
+        if solved == 0 {
+            return 0.0;
+        }
+        self.total_guesses as f64 / solved as f64
+    }
+}
+
+/// Plays every answer of `word_length` (or the first `limit`) with `strategy`.
+pub fn benchmark(
+    word: &Rc<Word>,
+    word_length: usize,
+    strategy: &mut dyn Strategy,
+    max_attempts: u8,
+    limit: Option<usize>,
+    matrix_mib: usize,
+) -> BenchmarkReport {
+    let answers = word.answer.get(word_length).cloned().unwrap_or_default();
+    let matrix = pattern_matrix(word, word_length, matrix_mib);
+    let opening = match (strategy.is_deterministic(), &matrix) {
+        (false, _) => None,
+        (true, Some(matrix)) => strategy.choose(&Solver::with_matrix(word, word_length, matrix)),
+        (true, None) => strategy.choose(&Solver::new(word, word_length)),
+    };
+    let mut report = BenchmarkReport {
+        strategy: strategy.name().to_string(),
+        ..Default::default()
+    };
+    let mut scores: Vec<(String, u8)> = Vec::new();
+    for answer in answers.iter().take(limit.unwrap_or(usize::MAX)) {
+        let mut game = Game::with_word(
+            Rc::clone(word),
+            GameOptions {
+                answer: Some(answer.clone()),
+                max_attempts: Some(max_attempts),
+                ..Default::default()
+            },
+        );
+        game.start();
+        report.games += 1;
+        match play(&mut game, strategy, opening.as_deref(), matrix.as_ref()) {
+            GameResult::Won => {
+                report.total_guesses += game.get_attempts() as usize;
+                scores.push((answer.clone(), game.get_attempts()));
+            }
+            GameResult::Lost => {
+                report.failures.push(answer.clone());
+                scores.push((answer.clone(), max_attempts.saturating_add(1)));
+            }
+        }
+    }
+    scores.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    scores.truncate(10);
+    report.worst = scores;
+    report
+}
+
+/// Every word a solver may guess at `word_length` (the dictionary's words of
+/// that length and the answers) judged against every answer, or `None` if
+/// that takes more than `max_mib` MiB or the words don't fit one.
+fn pattern_matrix(word: &Word, word_length: usize, max_mib: usize) -> Option<PatternMatrix> {
+    let answers = word.answer.get(word_length)?;
+    let guesses: BTreeSet<&str> = word
+        .dictionary
+        .iter()
+        .filter(|w| w.chars().count() == word_length)
+        .chain(answers.iter().map(String::as_str))
+        .collect();
+    let bytes = guesses.len().saturating_mul(answers.len()).saturating_mul(2);
+    if bytes > max_mib.saturating_mul(1024 * 1024) {
+        return None;
+    }
+    PatternMatrix::new(guesses.into_iter().map(String::from).collect(), answers.clone())
+}
@@ -1,4 +1,7 @@
+pub mod cli;
 pub mod game;
+pub mod paths;
+pub mod stats;
 pub mod types;
 pub mod word;
-pub mod ui;
\ No newline at end of file
+pub mod ui;
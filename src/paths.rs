@@ -0,0 +1,70 @@
+use std::env;
+use std::path::PathBuf;
+
+/// Where saves and statistics live: `$XDG_DATA_HOME/wordle`, falling back to
+/// `~/.local/share/wordle` (or `%APPDATA%\wordle` on Windows). The directory
+/// may not exist yet.
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_DATA_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("wordle"));
+    }
+    if let Some(dir) = env::var_os("APPDATA").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("wordle"));
+    }
+    env::var_os("HOME")
+        .filter(|d| !d.is_empty())
+        .map(|home| PathBuf::from(home).join(".local/share/wordle"))
+}
+
+/// Where the user's configuration lives: `$XDG_CONFIG_HOME/wordle`, falling
+/// back to `~/.config/wordle` (or `%APPDATA%\wordle` on Windows).
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("wordle"));
+    }
+    if let Some(dir) = env::var_os("APPDATA").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("wordle"));
+    }
+    env::var_os("HOME")
+        .filter(|d| !d.is_empty())
+        .map(|home| PathBuf::from(home).join(".config/wordle"))
+}
+
+/// The config file to read: `$WORDLE_CONFIG` if set, otherwise
+/// `config.toml` in `config_dir()`.
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("WORDLE_CONFIG").filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    config_dir().map(|dir| dir.join("config.toml"))
+}
+
+/// Directories searched for user-supplied word lists, first match wins:
+/// `$WORDLE_WORD_DIR`, then `wordle/word` under `data_dir()` and under each
+/// of `$XDG_DATA_DIRS` (default `/usr/local/share:/usr/share`).
+pub fn word_dirs() -> Vec<PathBuf> {
+    search_dirs("WORDLE_WORD_DIR", "word")
+}
+
+/// Directories searched for word packs, in the same order as `word_dirs`:
+/// `$WORDLE_PACK_DIR`, then `wordle/packs` under the data directories.
+pub fn pack_dirs() -> Vec<PathBuf> {
+    search_dirs("WORDLE_PACK_DIR", "packs")
+}
+
+fn search_dirs(var: &str, name: &str) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = env::var_os(var).filter(|d| !d.is_empty()) {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(dir) = data_dir() {
+        dirs.push(dir.join(name));
+    }
+    let system = env::var_os("XDG_DATA_DIRS")
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    for dir in env::split_paths(&system) {
+        dirs.push(dir.join("wordle").join(name));
+    }
+    dirs
+}
@@ -0,0 +1,310 @@
+use serde_json::{Value, json};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::{
+    game::{daily::Date, game::Game},
+    types::*,
+};
+
+/// One finished game, stored as a line of JSON.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameRecord {
+    pub date: Date,
+    pub word_length: usize,
+    pub difficulty: GameDifficulty,
+    pub attempts: u8,
+    pub result: GameResult,
+    pub duration: Duration,
+    pub hints: u32,
+    pub hint_cost: u32,
+    pub mode: GameMode,
+    /// Played as one puzzle of a blitz run.
+    pub blitz: bool,
+}
+
+impl GameRecord {
+    /// Returns `None` while the game is still running.
+    pub fn from_game(game: &Game, blitz: bool) -> Option<Self> {
+        let result = match game.get_state() {
+            GameState::Over(result) => result,
+            _ => return None,
+        };
+        Some(GameRecord {
+            date: Date::today(),
+            word_length: game.get_word_length(),
+            difficulty: game.get_difficulty(),
+            attempts: game.get_attempts(),
+            result,
+            duration: game.get_duration(),
+            hints: game.hints().len() as u32,
+            hint_cost: game.hint_cost(),
+            mode: game.get_mode(),
+            blitz,
+        })
+    }
+    fn to_json(&self) -> Value {
+        json!({
+            "date": self.date.to_string(),
+            "word_length": self.word_length,
+            "difficulty": self.difficulty.to_string(),
+            "attempts": self.attempts,
+            "result": match self.result {
+                GameResult::Won => "won",
+                GameResult::Lost => "lost",
+            },
+            "duration_ms": self.duration.as_millis() as u64,
+            "hints": self.hints,
+            "hint_cost": self.hint_cost,
+            "mode": self.mode.to_string(),
+            "blitz": self.blitz,
+        })
+    }
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(GameRecord {
+            date: Date::parse(value.get("date")?.as_str()?).ok()?,
+            word_length: value.get("word_length")?.as_u64()? as usize,
+            difficulty: value.get("difficulty")?.as_str()?.parse().ok()?,
+            attempts: value.get("attempts")?.as_u64()? as u8,
+            result: match value.get("result")?.as_str()? {
+                "won" => GameResult::Won,
+                "lost" => GameResult::Lost,
+                _ => return None,
+            },
+            duration: Duration::from_millis(value.get("duration_ms")?.as_u64()?),
+            // Older records predate hints.
+            hints: value.get("hints").and_then(Value::as_u64).unwrap_or(0) as u32,
+            hint_cost: value.get("hint_cost").and_then(Value::as_u64).unwrap_or(0) as u32,
+            // Older records predate modes and were all classic games.
+            mode: match value.get("mode") {
+                Some(mode) => mode.as_str()?.parse().ok()?,
+                None => GameMode::Classic,
+            },
+            blitz: value.get("blitz").and_then(Value::as_bool).unwrap_or(false),
+        })
+    }
+}
+
+/// Aggregated numbers for a set of games.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Summary {
+    pub played: u32,
+    pub won: u32,
+    /// Wins where at least one hint was used.
+    pub hinted_wins: u32,
+    /// What the hints of those wins cost.
+    pub hint_cost: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    /// `distribution[i]` is the number of games won in `i + 1` guesses.
+    pub distribution: Vec<u32>,
+    pub total_duration: Duration,
+}
+
+impl fmt::Display for Summary {
+    /// One-line overview, e.g. `Played 12  Win % 83  Streak 3  Max streak 5`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Played {}  Win % {}  Streak {}  Max streak {}",
+            self.played,
+            self.win_percent(),
+            self.current_streak,
+            self.max_streak
+        )?;
+        if self.hinted_wins > 0 {
+            write!(
+                f,
+                "  Hinted wins {} (cost {})",
+                self.hinted_wins, self.hint_cost
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Summary {
+    pub fn win_percent(&self) -> u32 {
+        if self.played == 0 {
+            return 0;
+        }
+        (self.won as f64 * 100.0 / self.played as f64).round() as u32
+    }
+}
+
+/// Every finished game, oldest first.
+pub struct Statistics {
+    records: Vec<GameRecord>,
+}
+
+pub fn stats_path() -> Option<PathBuf> {
+    crate::paths::data_dir().map(|dir| dir.join("stats.jsonl"))
+}
+
+impl Statistics {
+    /// Loads the default store. A missing or unreadable store counts as empty.
+    pub fn load() -> Self {
+        match stats_path() {
+            Some(path) => Self::load_from(&path),
+            None => Statistics { records: Vec::new() },
+        }
+    }
+    /// Lines that don't parse are skipped rather than failing the whole store.
+    pub fn load_from(path: &Path) -> Self {
+        let records = fs::read_to_string(path)
+            .map(|content| {
+                content
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+                    .filter_map(|value| GameRecord::from_json(&value))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Statistics { records }
+    }
+    pub fn records(&self) -> &[GameRecord] {
+        &self.records
+    }
+    /// Appends a finished game to the default store and to `self`.
+    pub fn record(&mut self, record: GameRecord) -> std::io::Result<()> {
+        let path = stats_path().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "No data directory for statistics.")
+        })?;
+        self.record_to(record, &path)
+    }
+    pub fn record_to(&mut self, record: GameRecord, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", record.to_json())?;
+        self.records.push(record);
+        Ok(())
+    }
+    /// Summary over the games played in `mode`, in or out of blitz runs,
+    /// of every word length or only one. Each mode keeps its own streak.
+    pub fn summary(&self, mode: GameMode, blitz: bool, word_length: Option<usize>) -> Summary {
+        let mut summary = Summary::default();
+        let mut streak = 0;
+        for record in self.records.iter().filter(|r| {
+            r.mode == mode && r.blitz == blitz && word_length.is_none_or(|len| r.word_length == len)
+        }) {
+            summary.played += 1;
+            summary.total_duration += record.duration;
+            match record.result {
+                GameResult::Won => {
+                    summary.won += 1;
+                    if record.hints > 0 {
+                        summary.hinted_wins += 1;
+                        summary.hint_cost += record.hint_cost;
+                    }
+                    streak += 1;
+                    summary.max_streak = summary.max_streak.max(streak);
+                    let index = record.attempts.max(1) as usize - 1;
+                    if summary.distribution.len() <= index {
+                        summary.distribution.resize(index + 1, 0);
+                    }
+                    summary.distribution[index] += 1;
+                }
+                GameResult::Lost => streak = 0,
+            }
+        }
+        summary.current_streak = streak;
+        summary
+    }
+    /// Every mode played, and whether in blitz runs, in the order first played.
+    pub fn modes(&self) -> Vec<(GameMode, bool)> {
+        let mut modes = Vec::new();
+        for record in &self.records {
+            if !modes.contains(&(record.mode, record.blitz)) {
+                modes.push((record.mode, record.blitz));
+            }
+        }
+        modes
+    }
+    /// Word lengths that have at least one game in `mode`, ascending.
+    pub fn word_lengths(&self, mode: GameMode, blitz: bool) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self
+            .records
+            .iter()
+            .filter(|r| r.mode == mode && r.blitz == blitz)
+            .map(|r| r.word_length)
+            .collect();
+        lengths.sort_unstable();
+        lengths.dedup();
+        lengths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(mode: GameMode, result: GameResult, hint_cost: u32) -> GameRecord {
+        GameRecord {
+            date: Date::parse("2025-03-01").unwrap(),
+            word_length: 5,
+            difficulty: GameDifficulty::Medium,
+            attempts: 4,
+            result,
+            duration: Duration::from_secs(60),
+            hints: (hint_cost > 0) as u32,
+            hint_cost,
+            mode,
+            blitz: false,
+        }
+    }
+
+    #[test]
+    fn modes_keep_separate_stats() {
+        let statistics = Statistics {
+            records: vec![
+                record(GameMode::Classic, GameResult::Won, 0),
+                record(GameMode::Absurdle, GameResult::Lost, 0),
+                record(GameMode::Classic, GameResult::Won, 0),
+            ],
+        };
+        let classic = statistics.summary(GameMode::Classic, false, None);
+        assert_eq!((classic.played, classic.won, classic.current_streak), (2, 2, 2));
+        let absurdle = statistics.summary(GameMode::Absurdle, false, Some(5));
+        assert_eq!((absurdle.played, absurdle.won), (1, 0));
+        assert_eq!(statistics.summary(GameMode::Classic, true, None).played, 0);
+        assert_eq!(
+            statistics.modes(),
+            [(GameMode::Classic, false), (GameMode::Absurdle, false)]
+        );
+    }
+
+    #[test]
+    fn hint_cost_counts_only_wins() {
+        let statistics = Statistics {
+            records: vec![
+                record(GameMode::Classic, GameResult::Won, 3),
+                record(GameMode::Classic, GameResult::Lost, 5),
+            ],
+        };
+        let summary = statistics.summary(GameMode::Classic, false, None);
+        assert_eq!((summary.hinted_wins, summary.hint_cost), (1, 3));
+    }
+
+    #[test]
+    fn records_round_trip_and_old_ones_are_classic() {
+        let fibble = GameRecord {
+            blitz: true,
+            ..record(GameMode::Fibble { lies: 2 }, GameResult::Won, 0)
+        };
+        assert_eq!(GameRecord::from_json(&fibble.to_json()), Some(fibble));
+
+        let mut old = record(GameMode::Classic, GameResult::Won, 0).to_json();
+        let map = old.as_object_mut().unwrap();
+        map.remove("mode");
+        map.remove("blitz");
+        assert_eq!(
+            GameRecord::from_json(&old),
+            Some(record(GameMode::Classic, GameResult::Won, 0))
+        );
+    }
+}
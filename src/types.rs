@@ -1,5 +1,9 @@
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+use crate::word::alphabet::to_upper;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum LetterState {
     Matched,
     Present,
@@ -7,11 +11,63 @@ pub enum LetterState {
 }
 
 pub type GuessResult = Vec<LetterState>;
+
+/// Mastermind-style feedback: how many letters are in the right spot and how
+/// many are in the answer elsewhere, without saying which.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CountResult {
+    pub matched: usize,
+    pub present: usize,
+}
+
+impl From<&GuessResult> for CountResult {
+    fn from(result: &GuessResult) -> Self {
+        let mut counts = CountResult::default();
+        for state in result {
+            match state {
+                LetterState::Matched => counts.matched += 1,
+                LetterState::Present => counts.present += 1,
+                LetterState::Absent => {}
+            }
+        }
+        counts
+    }
+}
+
+impl fmt::Display for CountResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} matched, {} present", self.matched, self.present)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GuessRecord {
+    pub word: String,
+    pub result: GuessResult,
+}
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum GameResult {
     Won,
     Lost,
 }
+/// Why a game ended in `GameResult::Lost`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LossReason {
+    OutOfGuesses,
+    OutOfTime,
+    GaveUp,
+}
+
+impl fmt::Display for LossReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::OutOfGuesses => "Out of guesses",
+            Self::OutOfTime => "Time's up",
+            Self::GaveUp => "Gave up",
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum GameState {
     Innit,
@@ -25,12 +81,258 @@ pub enum GameDifficulty {
     Hard,
 }
 
+impl fmt::Display for GameDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Easy => "easy",
+            Self::Medium => "medium",
+            Self::Hard => "hard",
+        })
+    }
+}
+
+impl FromStr for GameDifficulty {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Self::Easy),
+            "medium" => Ok(Self::Medium),
+            "hard" => Ok(Self::Hard),
+            _ => Err(format!("Unknown difficulty: {}", s)),
+        }
+    }
+}
+
+/// Rule variants layered on top of a normal game.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    /// The host picks the answer as late as possible, always giving the
+    /// feedback that keeps the most answers alive.
+    Absurdle,
+    /// `lies` tiles of every row (1 or 2) show false feedback.
+    Fibble { lies: u8 },
+    /// Only the number of matched and present letters is shown, see
+    /// `CountResult`.
+    Mastermind,
+}
+
+impl GameMode {
+    /// The title shown to players.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Classic => "Wordle",
+            Self::Absurdle => "Absurdle",
+            Self::Fibble { .. } => "Fibble",
+            Self::Mastermind => "Mastermind",
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Classic => f.write_str("classic"),
+            Self::Absurdle => f.write_str("absurdle"),
+            Self::Fibble { lies: 1 } => f.write_str("fibble"),
+            Self::Fibble { lies } => write!(f, "fibble-{}", lies),
+            Self::Mastermind => f.write_str("mastermind"),
+        }
+    }
+}
+
+impl FromStr for GameMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "classic" | "normal" | "wordle" => Ok(Self::Classic),
+            "absurdle" => Ok(Self::Absurdle),
+            "fibble" | "fibble-1" => Ok(Self::Fibble { lies: 1 }),
+            "fibble-2" => Ok(Self::Fibble { lies: 2 }),
+            "mastermind" | "jotto" => Ok(Self::Mastermind),
+            _ => Err(format!("Unknown mode: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ConsoleUiType {
     Ratatui,
     Plain,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum UiType {
     Console(ConsoleUiType),
     Gui,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HardModeViolation {
+    /// A letter revealed as matched was moved or dropped. `position` is 0-based.
+    MissingMatched { position: usize, letter: char },
+    /// A letter revealed as present (or matched) appears fewer times than required.
+    MissingPresent { letter: char, count: usize },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GuessError {
+    NotInProgress,
+    /// The time limit ran out before the guess; the game is now lost.
+    OutOfTime,
+    InvalidCharacters,
+    WrongLength { expected: usize, got: usize },
+    NotInDictionary,
+    HardModeViolation(HardModeViolation),
+    JudgeFailed,
+}
+
+impl fmt::Display for HardModeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMatched { position, letter } => write!(
+                f,
+                "{} letter must be {}.",
+                ordinal(position + 1),
+                to_upper(*letter)
+            ),
+            Self::MissingPresent { letter, count: 1 } => {
+                write!(f, "Guess must contain {}.", to_upper(*letter))
+            }
+            Self::MissingPresent { letter, count } => write!(
+                f,
+                "Guess must contain {} at least {} times.",
+                to_upper(*letter),
+                count
+            ),
+        }
+    }
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInProgress => f.write_str("Game is not in progress."),
+            Self::OutOfTime => f.write_str("Time's up!"),
+            Self::InvalidCharacters => f.write_str("Word contains characters that aren't letters of the alphabet."),
+            Self::WrongLength { expected, .. } => write!(f, "Input length must be {}.", expected),
+            Self::NotInDictionary => f.write_str("Word not found."),
+            Self::HardModeViolation(violation) => violation.fmt(f),
+            Self::JudgeFailed => f.write_str("Failed to judge the guess."),
+        }
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+/// What kind of help a player asks for. Each kind has a cost, so a hinted
+/// win can be told apart from a clean one and from a heavily assisted one.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HintKind {
+    /// Reveal one correct letter that hasn't been found yet.
+    RevealLetter,
+    /// Say how many answers are still possible.
+    CandidatesLeft,
+    /// Suggest a word that fits everything revealed so far.
+    SuggestWord,
+}
+
+impl HintKind {
+    pub fn cost(self) -> u32 {
+        match self {
+            Self::CandidatesLeft => 1,
+            Self::RevealLetter => 2,
+            Self::SuggestWord => 3,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Hint {
+    /// `position` is 0-based.
+    RevealLetter { position: usize, letter: char },
+    CandidatesLeft(usize),
+    SuggestWord(String),
+}
+
+impl Hint {
+    pub fn kind(&self) -> HintKind {
+        match self {
+            Self::RevealLetter { .. } => HintKind::RevealLetter,
+            Self::CandidatesLeft(_) => HintKind::CandidatesLeft,
+            Self::SuggestWord(_) => HintKind::SuggestWord,
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RevealLetter { position, letter } => write!(
+                f,
+                "{} letter is {}",
+                ordinal(position + 1),
+                to_upper(*letter)
+            ),
+            Self::CandidatesLeft(1) => f.write_str("1 possible answer left"),
+            Self::CandidatesLeft(n) => write!(f, "{} possible answers left", n),
+            Self::SuggestWord(word) => write!(f, "try {}", word.chars().map(to_upper).collect::<String>()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HintError {
+    NotInProgress,
+    NothingToReveal,
+    /// An Absurdle host hasn't settled on any of the unrevealed letters yet.
+    Undecided,
+    NoCandidates,
+}
+
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInProgress => f.write_str("Game is not in progress."),
+            Self::NothingToReveal => f.write_str("Every letter has already been found."),
+            Self::Undecided => f.write_str("The answer isn't settled yet, there is no letter to reveal."),
+            Self::NoCandidates => f.write_str("No word fits the feedback so far."),
+        }
+    }
+}
+
+impl std::error::Error for HintError {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AnswerError {
+    InvalidLength(usize),
+    NoWords(usize),
+    /// Fewer distinct answers of `length` than the boards that need one.
+    TooFewWords { length: usize, needed: usize },
+}
+
+impl fmt::Display for AnswerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => write!(f, "Invalid word length: {}.", len),
+            Self::NoWords(len) => write!(f, "No words available for length {}.", len),
+            Self::TooFewWords { length, needed } => {
+                write!(f, "Not enough answers of length {} for {} boards.", length, needed)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswerError {}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
@@ -2,6 +2,9 @@ use ratatui::style::{Color, Style};
 use std::str::FromStr;
 use core::fmt;
 
+use crate::game::share::ShareStyle;
+use crate::word::alphabet::Alphabet;
+
 #[derive(Debug, Clone, Copy)]
 pub enum ConsoleBackgroundColour {
     Green,
@@ -59,6 +62,29 @@ impl fmt::Display for ConsoleBackgroundColour {
 
 pub type ColourConfig = [ConsoleBackgroundColour; 3];
 
+/// QWERTY rows for the on-screen keyboard, with each row's indent in keys.
+pub const KEYBOARD_ROWS: [(&str, usize); 3] = [("qwertyuiop", 0), ("asdfghjkl", 1), ("zxcvbnm", 3)];
+
+/// `KEYBOARD_ROWS` reduced to the letters of `alphabet`, followed by the
+/// letters QWERTY doesn't have, ten to a row.
+pub fn keyboard_rows(alphabet: &Alphabet) -> Vec<(String, usize)> {
+    let letters = alphabet.letters();
+    let mut rows: Vec<(String, usize)> = KEYBOARD_ROWS
+        .iter()
+        .map(|(row, indent)| (row.chars().filter(|c| letters.contains(c)).collect::<String>(), *indent))
+        .filter(|(row, _)| !row.is_empty())
+        .collect();
+    let extra: Vec<char> = letters
+        .iter()
+        .copied()
+        .filter(|&c| !KEYBOARD_ROWS.iter().any(|(row, _)| row.contains(c)))
+        .collect();
+    for chunk in extra.chunks(10) {
+        rows.push((chunk.iter().collect(), 0));
+    }
+    rows
+}
+
 impl FromStr for ConsoleBackgroundColour {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -85,6 +111,17 @@ pub enum ConfigError {
     InvalidColor(String),
 }
 
+/// The bundled config, used when the user has none.
+const DEFAULT_CONFIG: &str = include_str!("../../../assets/console/config.toml");
+
+/// The user's config file (see `paths::config_path`) if there is one,
+/// otherwise the bundled config.
+fn read_config() -> String {
+    crate::paths::config_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .unwrap_or_else(|| DEFAULT_CONFIG.to_string())
+}
+
 pub fn load_color_config() -> ColourConfig {
     const DEFAULT: ColourConfig = [
         ConsoleBackgroundColour::Green,
@@ -92,10 +129,7 @@ pub fn load_color_config() -> ColourConfig {
         ConsoleBackgroundColour::Gray,
     ];
 
-    let content = match std::fs::read_to_string("assets/console/config.toml") {
-        Ok(c) => c,
-        Err(_) => return DEFAULT,
-    };
+    let content = read_config();
 
     let value: toml::Value = match toml::from_str(&content) {
         Ok(v) => v,
@@ -135,4 +169,18 @@ pub fn load_color_config() -> ColourConfig {
     };
 
     [matched, present, absent]
-}
\ No newline at end of file
+}
+
+pub fn load_share_style() -> ShareStyle {
+    read_config()
+        .parse::<toml::Value>()
+        .ok()
+        .and_then(|value| {
+            value
+                .get("console")
+                .and_then(|v| v.get("share_style"))
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse().ok())
+        })
+        .unwrap_or_default()
+}
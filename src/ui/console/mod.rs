@@ -1,3 +1,6 @@
 pub mod ratatui;
 pub mod config;
-pub mod plain_console;
\ No newline at end of file
+pub mod plain_console;
+pub mod multi_plain;
+pub mod multi_ratatui;
+pub mod pack_menu;
\ No newline at end of file
@@ -0,0 +1,116 @@
+use std::io::{Write, stdout};
+
+use crate::{
+    game::multi::MultiGame,
+    types::*,
+    ui::console::{
+        config::*,
+        plain_console::{print_guess, print_keyboard},
+    },
+};
+
+/// The plain console for multi-board games. Each guess prints one line with
+/// its result on every board, solved boards are left blank.
+pub struct App {
+    pub game: MultiGame,
+    pub colour_config: ColourConfig,
+}
+impl App {
+    pub fn from_game(game: MultiGame) -> Self {
+        App {
+            game,
+            colour_config: super::config::load_color_config(),
+        }
+    }
+    pub fn start(&mut self) {
+        self.game.start();
+        println!(
+            "Welcome to {}! {} boards, word length is {}, {} guesses. Good Luck!",
+            self.game.name(),
+            self.game.boards().len(),
+            ConsoleBackgroundColour::Red.colour_text(self.game.get_word_length().to_string().as_str()),
+            self.game.get_max_attempts()
+        );
+        if let Some(seed) = self.game.get_seed() {
+            println!("Seed: {} (play it again with the same seed)", seed);
+        }
+        if self.game.get_difficulty() == GameDifficulty::Hard {
+            println!("Hard mode: revealed hints must be used on every board.");
+        }
+        println!("Type :q to quit.");
+        self.print_header();
+        while self.game.get_state() == GameState::InProgress {
+            print!(
+                "{}/{} Enter your guess: ",
+                self.game.get_attempts() + 1,
+                self.game.get_max_attempts()
+            );
+            stdout().flush().expect("Failed to flush.");
+            let mut input = String::new();
+            let read = std::io::stdin()
+                .read_line(&mut input)
+                .expect("Failed to read line");
+            let input = input.trim();
+            if read == 0 || input == ":q" || input == ":quit" {
+                println!();
+                return;
+            }
+            match self.game.guess(input) {
+                Ok(results) => {
+                    self.print_row(&results);
+                    print_keyboard(&self.colour_config, self.game.alphabet(), &self.game.letter_states());
+                }
+                Err(GuessError::HardModeViolation(violation)) => {
+                    println!("Hard mode: {}", violation);
+                }
+                Err(err) => {
+                    println!("{}", err);
+                }
+            }
+        }
+        let answers: Vec<String> = self
+            .game
+            .boards()
+            .iter()
+            .map(|b| b.get_answer().to_string())
+            .collect();
+        match self.game.get_state() {
+            GameState::Over(GameResult::Won) => println!(
+                "Congratulations! All {} boards solved in {} guesses.",
+                answers.len(),
+                self.game.get_attempts()
+            ),
+            _ => println!(
+                "Game over! Solved {}/{}. The answers were: {}",
+                self.game.solved(),
+                answers.len(),
+                answers.join(", ")
+            ),
+        }
+    }
+    /// Board numbers above the columns `print_row` fills.
+    fn print_header(&self) {
+        let width = self.game.get_word_length();
+        let columns: Vec<String> = (1..=self.game.boards().len())
+            .map(|i| format!("{:<width$}", i, width = width))
+            .collect();
+        println!("      {}", columns.join("  "));
+    }
+    fn print_row(&self, results: &[Option<GuessResult>]) {
+        let word = self.game.guesses().last().map(String::as_str).unwrap_or_default();
+        print!("{:>5} ", format!("{}/{}", self.game.get_attempts(), self.game.get_max_attempts()));
+        for (board, result) in self.game.boards().iter().zip(results) {
+            match result {
+                Some(result) => print_guess(&self.colour_config, word, result),
+                None => print!("{}", " ".repeat(board.get_word_length())),
+            }
+            let mark = if result.is_some() && board.get_state() == GameState::Over(GameResult::Won) {
+                "✓ "
+            } else {
+                "  "
+            };
+            print!("{}", mark);
+        }
+        println!();
+    }
+}
@@ -0,0 +1,284 @@
+use std::io::stdout;
+use std::time::Duration;
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+    },
+};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{
+    game::{game::Game, multi::MultiGame},
+    types::*,
+    ui::console::{
+        config::*,
+        ratatui::{Cell, keyboard_lines, render_cell},
+    },
+};
+
+/// The terminal UI for multi-board games. Boards are tiled left to right and
+/// wrap onto more rows when the terminal is too narrow.
+pub struct App {
+    game: MultiGame,
+    colour_config: ColourConfig,
+
+    input: String,
+
+    show_warning: bool,
+    warning_message: Option<String>,
+    warning_ticks: u8,
+
+    game_over_message: Option<String>,
+    should_quit: bool,
+}
+
+impl App {
+    pub fn from_game(game: MultiGame) -> Self {
+        Self {
+            game,
+            colour_config: super::config::load_color_config(),
+            input: String::new(),
+            show_warning: false,
+            warning_message: None,
+            warning_ticks: 0,
+            game_over_message: None,
+            should_quit: false,
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.game.start();
+        enable_raw_mode().unwrap();
+        execute!(stdout(), EnterAlternateScreen).unwrap();
+
+        let mut terminal =
+            Terminal::new(CrosstermBackend::new(stdout())).unwrap();
+
+        while !self.should_quit {
+            terminal.draw(|f| self.draw(f)).unwrap();
+
+            if self.game_over_message.is_none()
+                && let GameState::Over(result) = self.game.get_state()
+            {
+                self.game_over_message = Some(match result {
+                    GameResult::Won => format!(
+                        "Congratulations! All {} boards solved in {} guesses.",
+                        self.game.boards().len(),
+                        self.game.get_attempts()
+                    ),
+                    GameResult::Lost => format!(
+                        "Game over! The answers were: {}",
+                        answers(&self.game).join(", ")
+                    ),
+                });
+            }
+
+            if event::poll(Duration::from_millis(120)).unwrap()
+                && let Event::Key(key) = event::read().unwrap()
+            {
+                self.handle_key(key.code, key.modifiers);
+            }
+
+            if self.warning_ticks > 0 {
+                self.warning_ticks -= 1;
+                self.show_warning = self.warning_ticks > 0;
+            }
+        }
+
+        disable_raw_mode().unwrap();
+        execute!(stdout(), LeaveAlternateScreen).unwrap();
+    }
+
+    /* ---------- input ---------- */
+
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        if modifiers.contains(KeyModifiers::CONTROL) {
+            match code {
+                KeyCode::Char('c') => self.should_quit = true,
+                KeyCode::Char('g') => self.game.give_up(),
+                _ => {}
+            }
+            return;
+        }
+        if code == KeyCode::Up
+            && let Some(last) = self.game.guesses().last()
+        {
+            self.input = last.clone();
+        }
+        if self.game_over_message.is_some() {
+            return;
+        }
+        match code {
+            KeyCode::Char(c) if self.input.chars().count() < self.game.get_word_length() => {
+                if let Some(c) = self.game.alphabet().fold_char(c) {
+                    self.input.push(c);
+                }
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Enter => match self.game.guess(&self.input) {
+                Ok(_) => self.input.clear(),
+                Err(err) => {
+                    self.warning_message = Some(match err {
+                        GuessError::HardModeViolation(violation) => format!("Hard mode: {}", violation),
+                        _ => format!("Error: {}", err),
+                    });
+                    self.show_warning = true;
+                    self.warning_ticks = 6;
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /* ---------- drawing ---------- */
+
+    fn board(&self, game: &Game) -> Vec<Vec<Cell>> {
+        let n = self.game.get_word_length();
+        let k = self.game.get_max_attempts() as usize;
+        let mut board: Vec<Vec<Cell>> = game
+            .history()
+            .iter()
+            .map(|record| {
+                record
+                    .word
+                    .chars()
+                    .zip(record.result.iter())
+                    .map(|(c, state)| Cell::Result(c, *state))
+                    .collect()
+            })
+            .collect();
+        if board.len() < k && game.get_state() == GameState::InProgress {
+            let mut row: Vec<Cell> = self.input.chars().map(Cell::Input).collect();
+            row.resize(n, Cell::Empty);
+            board.push(row);
+        }
+        board.resize(k, vec![Cell::Empty; n]);
+        board
+    }
+
+    fn draw(&self, f: &mut Frame) {
+        let board_width = self.game.get_word_length() as u16 * 3 + 2;
+        let board_height = self.game.get_max_attempts() as u16 + 2;
+        let count = self.game.boards().len();
+        let columns = (f.area().width / board_width).clamp(1, count as u16) as usize;
+        let rows = count.div_ceil(columns);
+
+        let layout = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(board_height * rows as u16),
+            Constraint::Length(keyboard_rows(self.game.alphabet()).len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+
+        self.draw_header(f, layout[0]);
+        for (i, game) in self.game.boards().iter().enumerate() {
+            let area = Rect {
+                x: layout[1].x + (i % columns) as u16 * board_width,
+                y: layout[1].y + (i / columns) as u16 * board_height,
+                width: board_width,
+                height: board_height,
+            }
+            .intersection(f.area());
+            self.draw_board(f, area, i, game);
+        }
+        f.render_widget(
+            Paragraph::new(keyboard_lines(
+                &self.colour_config,
+                self.game.alphabet(),
+                &self.game.letter_states(),
+            )),
+            layout[2],
+        );
+        self.draw_status(f, layout[3]);
+        let info = match self.game.get_state() {
+            GameState::Over(_) => "^C - quit game",
+            _ => "^C - quit, ^G - give up",
+        };
+        f.render_widget(Paragraph::new(info).style(Style::new().fg(Color::Green)), layout[4]);
+    }
+
+    fn draw_header(&self, f: &mut Frame, area: Rect) {
+        let mut title = self.game.name();
+        if self.game.get_difficulty() == GameDifficulty::Hard {
+            title.push_str(" (Hard)");
+        }
+        if let Some(seed) = self.game.get_seed() {
+            title.push_str(&format!(" - seed {}", seed));
+        }
+        title.push_str(&format!(
+            "   solved {}/{}   guess {}/{}",
+            self.game.solved(),
+            self.game.boards().len(),
+            self.game.get_attempts(),
+            self.game.get_max_attempts()
+        ));
+        f.render_widget(Paragraph::new(title), area);
+    }
+
+    fn draw_board(&self, f: &mut Frame, area: Rect, index: usize, game: &Game) {
+        let lines: Vec<Line> = self
+            .board(game)
+            .iter()
+            .map(|row| {
+                Line::from(
+                    row.iter()
+                        .map(|cell| {
+                            let (ch, style) = render_cell(&self.colour_config, cell);
+                            Span::styled(format!("[{}]", ch), style)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        let (title, border) = match game.get_state() {
+            GameState::Over(GameResult::Won) => (
+                format!("{} ✓ {}", index + 1, game.get_attempts()),
+                Style::default().fg(Color::Green),
+            ),
+            GameState::Over(GameResult::Lost) => (
+                format!("{} {}", index + 1, game.get_answer().to_uppercase()),
+                Style::default().fg(Color::Red),
+            ),
+            _ => (format!("{}", index + 1), Style::default()),
+        };
+        let p = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border)
+                .title(title),
+        );
+        f.render_widget(p, area);
+    }
+
+    fn draw_status(&self, f: &mut Frame, area: Rect) {
+        let (text, style) = if self.show_warning {
+            (
+                self.warning_message.clone().unwrap_or_default(),
+                Style::default().fg(Color::Red),
+            )
+        } else if let Some(msg) = self.game_over_message.clone() {
+            (msg, Style::default().fg(Color::Green))
+        } else {
+            (String::new(), Style::default())
+        };
+        f.render_widget(Paragraph::new(text).style(style), area);
+    }
+}
+
+fn answers(game: &MultiGame) -> Vec<String> {
+    game.boards()
+        .iter()
+        .map(|b| b.get_answer().to_uppercase())
+        .collect()
+}
@@ -0,0 +1,81 @@
+use std::io::stdout;
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+    },
+};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::word::pack::Pack;
+
+pub enum Choice<'a> {
+    BuiltIn,
+    Pack(&'a Pack),
+    Quit,
+}
+
+/// Asks which word list to play before a game starts, the built-in words
+/// first and then `packs`. `broken` is the number of installed packs that
+/// failed to load; they are only mentioned, not offered.
+pub fn choose(packs: &[Pack], broken: usize) -> Choice<'_> {
+    enable_raw_mode().unwrap();
+    execute!(stdout(), EnterAlternateScreen).unwrap();
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout())).unwrap();
+
+    let mut selected = 0;
+    let choice = loop {
+        terminal.draw(|f| draw(f, packs, broken, selected)).unwrap();
+        let Event::Key(key) = event::read().unwrap() else {
+            continue;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break Choice::Quit,
+            KeyCode::Esc | KeyCode::Char('q') => break Choice::Quit,
+            KeyCode::Up | KeyCode::Char('k') => selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1).min(packs.len()),
+            KeyCode::Enter if selected == 0 => break Choice::BuiltIn,
+            KeyCode::Enter => break Choice::Pack(&packs[selected - 1]),
+            _ => {}
+        }
+    };
+
+    disable_raw_mode().unwrap();
+    execute!(stdout(), LeaveAlternateScreen).unwrap();
+    choice
+}
+
+fn draw(f: &mut Frame, packs: &[Pack], broken: usize, selected: usize) {
+    let items = std::iter::once(String::from("Built-in words"))
+        .chain(packs.iter().map(|pack| pack.to_string()));
+    let mut lines: Vec<Line> = items
+        .enumerate()
+        .map(|(i, item)| match i == selected {
+            true => Line::styled(format!("> {}", item), Style::default().add_modifier(Modifier::REVERSED)),
+            false => Line::raw(format!("  {}", item)),
+        })
+        .collect();
+    lines.push(Line::raw(""));
+    if broken > 0 {
+        lines.push(Line::styled(
+            format!("{} pack(s) failed to load, run 'wordle packs' to see why.", broken),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    lines.push(Line::styled(
+        "↑/↓ - select, Enter - play, Esc - quit",
+        Style::default().fg(Color::Green),
+    ));
+    let p = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Choose a word list"),
+    );
+    f.render_widget(p, f.area());
+}
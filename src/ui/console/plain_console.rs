@@ -1,65 +1,383 @@
+use std::collections::{HashMap, HashSet};
 use std::io::{Write, stdout};
+use std::time::Duration;
 
 use crate::{
-    game::game::Game,
+    game::{
+        blitz::Blitz,
+        game::{Game, GameOptions},
+        save,
+        share,
+    },
+    stats::stats::{GameRecord, Statistics},
     types::*,
     ui::console::config::*,
+    word::alphabet::{Alphabet, to_upper},
 };
 pub struct App {
     pub game: Game,
     pub colour_config: ColourConfig,
+    /// Fibble: tiles (row, position) the player suspects are lies.
+    suspects: HashSet<(usize, usize)>,
+    /// Set when playing a blitz run; `game` is then its current puzzle.
+    blitz: Option<Blitz>,
 }
 impl App {
-    pub fn new(difficulty: &crate::types::GameDifficulty) -> Self {
+    pub fn new(difficulty: &crate::types::GameDifficulty, seed: Option<u64>) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_options(GameOptions {
+            difficulty: Some(*difficulty),
+            seed,
+            ..Default::default()
+        })
+    }
+    pub fn with_options(options: GameOptions) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::from_game(Game::with_options(options)?))
+    }
+    pub fn from_game(game: Game) -> Self {
         let colour_config = super::config::load_color_config();
-        return App {
-            game: Game::new(None, Some(*difficulty), None),
-            colour_config: colour_config,
-        };
+        App {
+            game,
+            colour_config,
+            suspects: HashSet::new(),
+            blitz: None,
+        }
+    }
+    /// Plays a blitz run, starting its clock right away.
+    pub fn from_blitz(mut blitz: Blitz) -> Self {
+        let game = blitz.next_game().expect("A new blitz run has time left.");
+        let mut app = Self::from_game(game);
+        app.blitz = Some(blitz);
+        app
     }
     pub fn start(&mut self) {
+        if self.blitz.is_none() {
+            self.offer_resume();
+        }
+        // Only now, so the time limit doesn't run while the prompt waits.
         self.game.start();
         println!(
-            "Welcome to Wordle! Word length is {}. Good Luck!",
+            "Welcome to {}! Word length is {}. Good Luck!",
+            self.game.get_mode().name(),
             ConsoleBackgroundColour::Red.colour_text(self.game.get_word_length().to_string().as_str()),
         );
+        if let Some(daily) = self.game.get_daily() {
+            println!("Daily puzzle #{} ({})", daily.number(), daily.date());
+        } else if let Some(seed) = self.game.get_seed() {
+            println!("Seed: {} (play it again with the same seed)", seed);
+        }
+        if self.game.get_difficulty() == GameDifficulty::Hard {
+            println!("Hard mode: revealed hints must be used in every later guess.");
+        }
+        match self.game.get_mode() {
+            GameMode::Absurdle => {
+                println!("Absurdle: the answer keeps changing to dodge your guesses for as long as it can.")
+            }
+            GameMode::Fibble { lies } => {
+                println!("Fibble: {} tile(s) of every row lie. Mark suspects with :mark ROW POSITION.", lies)
+            }
+            GameMode::Mastermind => {
+                println!("Mastermind: each guess only shows how many letters are matched and how many are present.")
+            }
+            GameMode::Classic => {}
+        }
+        if let Some(blitz) = &self.blitz {
+            println!(
+                "Blitz: solve as many puzzles in a row as you can in {}.",
+                clock(blitz.limit())
+            );
+        } else if let Some(limit) = self.game.get_time_limit() {
+            println!("You have {} to solve it.", clock(limit));
+        }
+        println!("Type :hint for help or :q to save and quit.");
+        loop {
+            if !self.play() {
+                return;
+            }
+            if let GameState::Over(result) = self.game.get_state() {
+                match result {
+                    GameResult::Won => println!("Congratulations! You won!"),
+                    GameResult::Lost if self.game.loss_reason() == Some(LossReason::OutOfTime) => {
+                        println!("Time's up! The correct answer was: {}", self.game.get_answer())
+                    }
+                    GameResult::Lost => println!(
+                        "Game over! The correct answer was: {}",
+                        self.game.get_answer()
+                    ),
+                }
+            }
+            if self.game.lies().is_some() {
+                println!("Tiles that lied are marked with !, your suspects with ?:");
+                self.print_marks();
+            }
+            let Some(blitz) = &mut self.blitz else {
+                break;
+            };
+            blitz.record(&self.game);
+            record_stats(&self.game, true);
+            match blitz.next_game() {
+                Some(game) => {
+                    println!(
+                        "\nPuzzle {} ({} left). Word length is {}.",
+                        blitz.solved() + 1,
+                        clock(blitz.remaining()),
+                        game.get_word_length()
+                    );
+                    self.game = game;
+                    self.suspects.clear();
+                }
+                None => {
+                    println!(
+                        "\nBlitz over! Solved {} puzzle(s) in a row with {} guesses.",
+                        blitz.solved(),
+                        blitz.guesses()
+                    );
+                    return;
+                }
+            }
+        }
+        if let Err(err) = save::discard() {
+            println!("{}", err);
+        }
+        if let Some(text) = share::share_text(&self.game, load_share_style()) {
+            println!("\n{}\n", text);
+        }
+        self.print_stats();
+    }
+    /// Reads guesses until the game is over. Returns `false` if the player
+    /// quit instead.
+    fn play(&mut self) -> bool {
         while self.game.get_state() == GameState::InProgress {
+            let timer = match self.game.remaining() {
+                Some(remaining) => format!(" [{}]", clock(remaining)),
+                None => String::new(),
+            };
             print!(
-                "{}/{} Enter your guess: ",
+                "{}/{}{} Enter your guess: ",
                 self.game.get_attempts() + 1,
-                self.game.get_max_attempts()
+                self.game.get_max_attempts(),
+                timer
             );
             stdout().flush().expect("Failed to flush.");
             let mut input = String::new();
-            std::io::stdin()
+            let read = std::io::stdin()
                 .read_line(&mut input)
                 .expect("Failed to read line");
             let input = input.trim();
+            if read == 0 || input == ":q" || input == ":quit" {
+                self.save_and_quit();
+                return false;
+            }
+            if let Some(kind) = input.strip_prefix(":hint") {
+                self.give_hint(kind.trim());
+                continue;
+            }
+            if let Some(tile) = input.strip_prefix(":mark") {
+                self.mark(tile.trim());
+                continue;
+            }
             match self.game.guess(input) {
                 Ok(result) => {
-                    for i in 0..input.len() {
-                        match result[i] {
-                            LetterState::Matched => print!("{}", self.colour_config[0].colour_text(input.chars().nth(i).unwrap().to_string().as_str())),
-                            LetterState::Present => print!("{}", self.colour_config[1].colour_text(input.chars().nth(i).unwrap().to_string().as_str())),
-                            LetterState::Absent => print!("{}", self.colour_config[2].colour_text(input.chars().nth(i).unwrap().to_string().as_str())),
-                        }
+                    // The guess as played, folded through the alphabet.
+                    let word = self.game.last_guess().map(|r| r.word.clone()).unwrap_or_default();
+                    self.print_row(&word, &result);
+                    println!();
+                    if self.game.get_mode() != GameMode::Mastermind {
+                        print_keyboard(&self.colour_config, self.game.get_word().alphabet(), &self.game.letter_states());
                     }
                 }
+                Err(GuessError::HardModeViolation(violation)) => {
+                    println!("Hard mode: {}", violation);
+                }
                 Err(err) => {
                     println!("{}", err);
                 }
             }
             println!();
         }
-        match self.game.get_state() {
-            GameState::Over(result) => match result {
-                GameResult::Won => println!("Congratulations! You won!"),
-                GameResult::Lost => println!(
-                    "Game over! The correct answer was: {}",
-                    self.game.get_answer()
-                ),
-            },
-            _ => {}
+        true
+    }
+    fn print_stats(&self) {
+        let statistics = record_stats(&self.game, false);
+        let summary = statistics.summary(self.game.get_mode(), false, Some(self.game.get_word_length()));
+        println!("{}", summary);
+        let widest = summary.distribution.iter().copied().max().unwrap_or(0).max(1);
+        for (i, count) in summary.distribution.iter().enumerate() {
+            let width = (*count as usize * 20).div_ceil(widest as usize);
+            println!("{:>2} {} {}", i + 1, "#".repeat(width), count);
         }
     }
+    fn offer_resume(&mut self) {
+        if !save::has_save() {
+            return;
+        }
+        let saved = match save::load() {
+            Ok(saved) => saved,
+            Err(err) => {
+                println!("Saved game discarded: {}", err);
+                let _ = save::discard();
+                return;
+            }
+        };
+        print!(
+            "Resume saved game ({} letters, {}/{})? [y/N] ",
+            saved.get_word_length(),
+            saved.get_attempts(),
+            saved.get_max_attempts()
+        );
+        stdout().flush().expect("Failed to flush.");
+        let mut answer = String::new();
+        std::io::stdin()
+            .read_line(&mut answer)
+            .expect("Failed to read line");
+        if answer.trim().eq_ignore_ascii_case("y") {
+            self.game = saved;
+            for record in self.game.history() {
+                self.print_row(&record.word, &record.result);
+                println!();
+            }
+        } else {
+            let _ = save::discard();
+        }
+    }
+    /// `:hint letter`, `:hint count` or `:hint word`. A bare `:hint` lists them.
+    fn give_hint(&mut self, kind: &str) {
+        let kind = match kind {
+            "letter" | "1" => HintKind::RevealLetter,
+            "count" | "2" => HintKind::CandidatesLeft,
+            "word" | "3" => HintKind::SuggestWord,
+            _ => {
+                println!(
+                    "Hints: ':hint letter' reveals a letter (cost {}), ':hint count' counts possible answers (cost {}), ':hint word' suggests a word (cost {}).",
+                    HintKind::RevealLetter.cost(),
+                    HintKind::CandidatesLeft.cost(),
+                    HintKind::SuggestWord.cost()
+                );
+                return;
+            }
+        };
+        match self.game.hint(kind) {
+            Ok(hint) => println!("Hint: {} (total cost {})", hint, self.game.hint_cost()),
+            Err(err) => println!("Hint: {}", err),
+        }
+    }
+    /// One guess: coloured tiles, or in Mastermind the plain word followed
+    /// by its matched and present counts.
+    fn print_row(&self, word: &str, result: &GuessResult) {
+        if self.game.get_mode() != GameMode::Mastermind {
+            print_guess(&self.colour_config, word, result);
+            return;
+        }
+        let counts = CountResult::from(result);
+        print!(
+            "{}  {} {}",
+            word,
+            self.colour_config[0].colour_text(&format!(" {} ", counts.matched)),
+            self.colour_config[1].colour_text(&format!(" {} ", counts.present))
+        );
+    }
+    /// `:mark ROW POSITION` toggles a suspected lie, both 1-based.
+    fn mark(&mut self, tile: &str) {
+        if !matches!(self.game.get_mode(), GameMode::Fibble { .. }) {
+            println!("Only Fibble tiles can lie.");
+            return;
+        }
+        let numbers: Vec<usize> = tile.split_whitespace().filter_map(|n| n.parse().ok()).collect();
+        let (row, position) = match numbers[..] {
+            [row, position]
+                if (1..=self.game.get_attempts() as usize).contains(&row)
+                    && (1..=self.game.get_word_length()).contains(&position) =>
+            {
+                (row - 1, position - 1)
+            }
+            _ => {
+                println!("Usage: :mark ROW POSITION, e.g. ':mark 2 3' for the 3rd letter of the 2nd guess.");
+                return;
+            }
+        };
+        if !self.suspects.remove(&(row, position)) {
+            self.suspects.insert((row, position));
+        }
+        self.print_marks();
+    }
+    /// Prints the guesses with `?` under suspected tiles and, once the game
+    /// is over, `!` under the tiles that lied.
+    fn print_marks(&self) {
+        let lies = self.game.lies();
+        for (r, record) in self.game.history().iter().enumerate() {
+            print_guess(&self.colour_config, &record.word, &record.result);
+            println!();
+            let marks: String = (0..record.result.len())
+                .map(|c| {
+                    if lies.is_some_and(|lies| lies[r].contains(&c)) {
+                        '!'
+                    } else if self.suspects.contains(&(r, c)) {
+                        '?'
+                    } else {
+                        ' '
+                    }
+                })
+                .collect();
+            if !marks.trim().is_empty() {
+                println!("{}", marks.trim_end());
+            }
+        }
+    }
+    fn save_and_quit(&self) {
+        println!();
+        if self.game.get_attempts() == 0 || self.blitz.is_some() {
+            return;
+        }
+        match save::save(&self.game) {
+            Ok(()) => println!("Game saved. It will be offered again on the next launch."),
+            Err(err) => println!("{}", err),
+        }
+    }
+}
+
+/// Prints the QWERTY rows with every guessed letter coloured by its best
+/// known state.
+pub(super) fn print_keyboard(colour_config: &ColourConfig, alphabet: &Alphabet, states: &HashMap<char, LetterState>) {
+    for (row, indent) in keyboard_rows(alphabet) {
+        print!("{}", " ".repeat(indent));
+        for c in row.chars() {
+            let key = to_upper(c).to_string();
+            match states.get(&c) {
+                Some(LetterState::Matched) => print!("{}", colour_config[0].colour_text(&key)),
+                Some(LetterState::Present) => print!("{}", colour_config[1].colour_text(&key)),
+                Some(LetterState::Absent) => print!("{}", colour_config[2].colour_text(&key)),
+                None => print!("{}", key),
+            }
+            print!(" ");
+        }
+        println!();
+    }
+}
+pub(super) fn print_guess(colour_config: &ColourConfig, word: &str, result: &GuessResult) {
+    for (c, state) in word.chars().zip(result.iter()) {
+        match state {
+            LetterState::Matched => print!("{}", colour_config[0].colour_text(c.to_string().as_str())),
+            LetterState::Present => print!("{}", colour_config[1].colour_text(c.to_string().as_str())),
+            LetterState::Absent => print!("{}", colour_config[2].colour_text(c.to_string().as_str())),
+        }
+    }
+}
+/// Adds a finished game to the statistics file, reporting failures, and
+/// returns the updated statistics.
+fn record_stats(game: &Game, blitz: bool) -> Statistics {
+    let mut statistics = Statistics::load();
+    if let Some(record) = GameRecord::from_game(game, blitz)
+        && let Err(err) = statistics.record(record)
+    {
+        println!("Failed to record statistics: {}", err);
+    }
+    statistics
+}
+/// `m:ss`, or `h:mm:ss` from an hour up.
+pub(super) fn clock(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
 }
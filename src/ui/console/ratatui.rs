@@ -1,4 +1,5 @@
-use std::io::stdout;
+use std::collections::{HashMap, HashSet};
+use std::io::{Write, stdout};
 use std::time::Duration;
 
 use crossterm::{
@@ -17,16 +18,25 @@ use ratatui::{
 use crossterm::event::KeyModifiers;
 
 use crate::{
-    game::game::Game,
+    game::{
+        blitz::Blitz,
+        game::{Game, GameOptions},
+        save,
+        share::{self, ShareStyle},
+    },
+    stats::stats::{GameRecord, Statistics, Summary},
     types::*,
-    ui::console::config::*,
+    ui::console::{config::*, plain_console::clock},
+    word::alphabet::{Alphabet, to_upper},
 };
 
 #[derive(Clone, Copy)]
-enum Cell {
+pub(super) enum Cell {
     Empty,
     Input(char),
     Result(char, LetterState),
+    /// A played guess whose letters get no feedback of their own (Mastermind).
+    Played(char),
 }
 
 pub struct App {
@@ -34,42 +44,76 @@ pub struct App {
     difficulty: GameDifficulty,
     game: Game,
     colour_config: ColourConfig,
+    share_style: ShareStyle,
 
-    board: Vec<Vec<Cell>>,
     input: String,
-    last_guess: String,
 
     show_warning: bool,
     warning_message: Option<String>,
     warning_ticks: u8,
+    warning_colour: Color,
 
     game_over_message: Option<String>,
+    /// Shown on the post-game screen once the finished game is recorded.
+    stats: Option<Summary>,
+    /// The hint menu is open and waiting for a choice.
+    hint_menu: bool,
+    /// Fibble: tiles (row, position) the player suspects are lies.
+    suspects: HashSet<(usize, usize)>,
+    /// Fibble: the tile selected while marking suspects, `None` when not marking.
+    mark_cursor: Option<(usize, usize)>,
+    /// A saved game waiting for the player to accept or decline resuming it.
+    pending_resume: Option<Game>,
+    /// Set when playing a blitz run; `game` is then its current puzzle.
+    blitz: Option<Blitz>,
     should_quit: bool,
 }
 
 impl App {
-    pub fn new(difficulty: &GameDifficulty) -> Self {
-        let colour_config = super::config::load_color_config();
-        let game = Game::new(None, Some(*difficulty), None);
+    pub fn new(difficulty: &GameDifficulty, seed: Option<u64>) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_options(GameOptions {
+            difficulty: Some(*difficulty),
+            seed,
+            ..Default::default()
+        })
+    }
+
+    pub fn with_options(options: GameOptions) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::from_game(Game::with_options(options)?))
+    }
 
-        let n = game.get_word_length();
-        let k = game.get_max_attempts();
+    pub fn from_game(game: Game) -> Self {
+        let colour_config = super::config::load_color_config();
 
         Self {
-            difficulty: *difficulty,
-            game: game,
-            colour_config: colour_config,
-            board: vec![vec![Cell::Empty; n]; k as usize],
+            difficulty: game.get_difficulty(),
+            game,
+            colour_config,
+            share_style: load_share_style(),
             input: String::new(),
-            last_guess: String::new(),
             warning_message: None,
             show_warning: false,
             warning_ticks: 0,
+            warning_colour: Color::Red,
             game_over_message: None,
+            stats: None,
+            hint_menu: false,
+            suspects: HashSet::new(),
+            mark_cursor: None,
+            pending_resume: None,
+            blitz: None,
             should_quit: false,
         }
     }
 
+    /// Plays a blitz run, starting its clock right away.
+    pub fn from_blitz(mut blitz: Blitz) -> Self {
+        let game = blitz.next_game().expect("A new blitz run has time left.");
+        let mut app = Self::from_game(game);
+        app.blitz = Some(blitz);
+        app
+    }
+
     /* ---------- terminal lifecycle ---------- */
 
     fn enter_terminal() {
@@ -85,7 +129,20 @@ impl App {
     /* ---------- main loop ---------- */
 
     pub fn start(&mut self) {
-        self.game.start();
+        if self.blitz.is_none() && save::has_save() {
+            match save::load() {
+                Ok(saved) => self.pending_resume = Some(saved),
+                Err(err) => {
+                    let _ = save::discard();
+                    self.warning_message = Some(format!("Saved game discarded: {}", err));
+                    self.flash_warning();
+                }
+            }
+        }
+        // Otherwise the clock starts once the resume prompt is answered.
+        if self.pending_resume.is_none() {
+            self.game.start();
+        }
         Self::enter_terminal();
 
         let mut terminal =
@@ -95,50 +152,171 @@ impl App {
             if self.should_quit {
                 break;
             }
+            self.game.tick();
             terminal.draw(|f| self.draw(f)).unwrap();
 
-            if self.game_over_message.is_none() {
-                if let GameState::Over(result) = self.game.get_state() {
-                    self.game_over_message = Some(match result {
-                        GameResult::Won => String::from("Congratulations!"),
-                        GameResult::Lost => format!(
-                            "Game over! The correct answer was: {}",
-                            self.game.get_answer()
-                        ),
-                    });
+            if self.game_over_message.is_none()
+                && let GameState::Over(result) = self.game.get_state()
+            {
+                self.game_over_message = Some(match result {
+                    GameResult::Won => String::from("Congratulations!"),
+                    GameResult::Lost if self.game.loss_reason() == Some(LossReason::OutOfTime) => {
+                        format!("Time's up! The correct answer was: {}", self.game.get_answer())
+                    }
+                    GameResult::Lost => format!(
+                        "Game over! The correct answer was: {}",
+                        self.game.get_answer()
+                    ),
+                });
+                if self.game.lies().is_some()
+                    && let Some(message) = &mut self.game_over_message
+                {
+                    message.push_str(" Tiles that lied are shown as !x!.");
                 }
+                self.record_stats();
+                self.next_blitz_puzzle();
             }
 
-            if event::poll(Duration::from_millis(120)).unwrap() {
-                if let Event::Key(key) = event::read().unwrap() {
-                    self.handle_key(key.code, key.modifiers);
-                }
+            if event::poll(Duration::from_millis(120)).unwrap()
+                && let Event::Key(key) = event::read().unwrap()
+            {
+                self.handle_key(key.code, key.modifiers);
             }
 
-            self.sync_input_to_board();
             self.tick_warning();
         }
 
         Self::leave_terminal();
+
+        // Quitting at the resume prompt keeps the saved game for next time.
+        if self.blitz.is_some() || self.pending_resume.is_some() {
+            return;
+        }
+        let result = if self.game.get_state() == GameState::InProgress
+            && self.game.get_attempts() > 0
+        {
+            save::save(&self.game)
+        } else {
+            save::discard()
+        };
+        if let Err(err) = result {
+            eprintln!("{}", err);
+        }
+    }
+
+    fn record_stats(&mut self) {
+        let mut statistics = Statistics::load();
+        let blitz = self.blitz.is_some();
+        if let Some(record) = GameRecord::from_game(&self.game, blitz)
+            && let Err(err) = statistics.record(record)
+        {
+            self.warning_message = Some(format!("Failed to record statistics: {}", err));
+            self.flash_warning();
+        }
+        self.stats = Some(statistics.summary(self.game.get_mode(), blitz, Some(self.game.get_word_length())));
+    }
+
+    /// Moves a blitz run on to its next puzzle once the current one is
+    /// over, or ends the run.
+    fn next_blitz_puzzle(&mut self) {
+        let Some(blitz) = &mut self.blitz else {
+            return;
+        };
+        blitz.record(&self.game);
+        match blitz.next_game() {
+            Some(game) => {
+                let solved = blitz.solved();
+                self.game = game;
+                self.game_over_message = None;
+                self.stats = None;
+                self.input.clear();
+                self.suspects.clear();
+                self.mark_cursor = None;
+                self.flash_notice(format!("Solved! {} in a row so far.", solved));
+            }
+            None => {
+                self.game_over_message = Some(format!(
+                    "Blitz over! Solved {} puzzle(s) in a row with {} guesses. {}",
+                    blitz.solved(),
+                    blitz.guesses(),
+                    self.game_over_message.take().unwrap_or_default()
+                ));
+            }
+        }
     }
 
     /* ---------- input ---------- */
 
     fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
         if modifiers.contains(KeyModifiers::CONTROL) {
-            if let KeyCode::Char('c') = code {
-                self.should_quit = true;
-                return;
+            match code {
+                KeyCode::Char('c') => self.should_quit = true,
+                KeyCode::Char('g') if self.pending_resume.is_none() => self.game.give_up(),
+                KeyCode::Char('y') => self.copy_result(),
+                KeyCode::Char('s') => self.write_result(),
+                KeyCode::Char('t') => self.open_hint_menu(),
+                _ => {}
+            }
+            return;
+        }
+        if self.pending_resume.is_some() {
+            match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                    self.game = self.pending_resume.take().unwrap();
+                    self.difficulty = self.game.get_difficulty();
+                    self.game.start();
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') => {
+                    self.pending_resume = None;
+                    let _ = save::discard();
+                    self.game.start();
+                }
+                _ => {}
+            }
+            return;
+        }
+        if self.hint_menu {
+            self.hint_menu = false;
+            let kind = match code {
+                KeyCode::Char('1') => HintKind::RevealLetter,
+                KeyCode::Char('2') => HintKind::CandidatesLeft,
+                KeyCode::Char('3') => HintKind::SuggestWord,
+                _ => return,
+            };
+            if let Err(err) = self.game.hint(kind) {
+                self.warning_message = Some(format!("Hint: {}", err));
+                self.flash_warning();
             }
-            if let KeyCode::Char('g') = code {
-                self.game.give_up();
-                return;
+            return;
+        }
+        if let Some((row, col)) = self.mark_cursor {
+            let last_row = (self.game.get_attempts() as usize).saturating_sub(1);
+            let last_col = self.game.get_word_length() - 1;
+            match code {
+                KeyCode::Left => self.mark_cursor = Some((row, col.saturating_sub(1))),
+                KeyCode::Right => self.mark_cursor = Some((row, (col + 1).min(last_col))),
+                KeyCode::Up => self.mark_cursor = Some((row.saturating_sub(1), col)),
+                KeyCode::Down => self.mark_cursor = Some(((row + 1).min(last_row), col)),
+                KeyCode::Char(' ') | KeyCode::Enter => self.toggle_suspect(row, col),
+                KeyCode::Tab | KeyCode::Esc => self.mark_cursor = None,
+                _ => {}
             }
+            return;
         }
-        if code == KeyCode::Up {
-            if self.last_guess != String::from("") {
-                self.input = self.last_guess.clone();
+        if code == KeyCode::Tab {
+            if matches!(self.game.get_mode(), GameMode::Fibble { .. }) && self.game.get_attempts() > 0 {
+                self.mark_cursor = Some((self.game.get_attempts() as usize - 1, 0));
             }
+            return;
+        }
+        if code == KeyCode::Char('?') {
+            self.open_hint_menu();
+            return;
+        }
+        if code == KeyCode::Up
+            && let Some(last) = self.game.last_guess()
+        {
+            self.input = last.word.clone();
         }
         if self.game_over_message.is_some() {
             return;
@@ -147,10 +325,10 @@ impl App {
         let n = self.game.get_word_length();
 
         match code {
-            KeyCode::Char(c)
-                if c.is_ascii_alphabetic() && self.input.len() < n =>
-            {
-                self.input.push(c.to_ascii_lowercase());
+            KeyCode::Char(c) if self.input.chars().count() < n => {
+                if let Some(c) = self.game.get_word().alphabet().fold_char(c) {
+                    self.input.push(c);
+                }
             }
             KeyCode::Backspace => {
                 self.input.pop();
@@ -162,38 +340,85 @@ impl App {
         }
     }
 
-    fn submit(&mut self) {
-        let row = self.game.get_attempts();
+    fn toggle_suspect(&mut self, row: usize, col: usize) {
+        if !self.suspects.remove(&(row, col)) {
+            self.suspects.insert((row, col));
+        }
+    }
 
+    fn open_hint_menu(&mut self) {
+        if self.pending_resume.is_none() && self.game.get_state() == GameState::InProgress {
+            self.hint_menu = true;
+        }
+    }
+
+    fn submit(&mut self) {
         match self.game.guess(&self.input) {
-            Ok(result) => {
-                for (col, state) in result.iter().enumerate() {
-                    let ch = self.input.chars().nth(col).unwrap();
-                    self.board[row as usize][col] = Cell::Result(ch, *state);
-                }
-                self.last_guess = self.input.clone();
+            Ok(_) => {
                 self.input.clear();
             }
             Err(err) => {
-                self.warning_message = Some(String::from(format!("Error: {}", err)));
+                self.warning_message = Some(match err {
+                    GuessError::HardModeViolation(violation) => format!("Hard mode: {}", violation),
+                    _ => format!("Error: {}", err),
+                });
                 self.flash_warning()
             },
         }
     }
 
-    fn sync_input_to_board(&mut self) {
-        let row = self.game.get_attempts();
+    /// Builds the grid from the game history, the row being typed and the
+    /// remaining empty rows.
+    fn board(&self) -> Vec<Vec<Cell>> {
         let n = self.game.get_word_length();
+        let k = self.game.get_max_attempts() as usize;
+
+        let hidden = self.game.get_mode() == GameMode::Mastermind;
+        let mut board: Vec<Vec<Cell>> = self
+            .game
+            .history()
+            .iter()
+            .map(|record| {
+                record
+                    .word
+                    .chars()
+                    .zip(record.result.iter())
+                    .map(|(c, state)| if hidden { Cell::Played(c) } else { Cell::Result(c, *state) })
+                    .collect()
+            })
+            .collect();
+        if board.len() < k && self.game.get_state() == GameState::InProgress {
+            let mut row: Vec<Cell> = self.input.chars().map(Cell::Input).collect();
+            row.resize(n, Cell::Empty);
+            board.push(row);
+        }
+        board.resize(k, vec![Cell::Empty; n]);
+        board
+    }
+
+    /* ---------- sharing ---------- */
 
-        if row >= self.board.len() as u8 {
+    fn copy_result(&mut self) {
+        let Some(text) = share::share_text(&self.game, self.share_style) else {
             return;
+        };
+        let mut out = stdout();
+        if out.write_all(share::osc52(&text).as_bytes()).and_then(|_| out.flush()).is_ok() {
+            self.flash_notice(String::from("Result copied to clipboard."));
         }
+    }
 
-        for col in 0..n {
-            self.board[row as usize][col] = match self.input.chars().nth(col) {
-                Some(c) => Cell::Input(c),
-                None => Cell::Empty,
-            };
+    fn write_result(&mut self) {
+        let Some(text) = share::share_text(&self.game, self.share_style) else {
+            return;
+        };
+        let path = "wordle-result.txt";
+        match std::fs::write(path, text + "\n") {
+            Ok(()) => self.flash_notice(format!("Result written to {}.", path)),
+            Err(err) => {
+                self.warning_message = Some(format!("Failed to write {}: {}", path, err));
+                self.flash_warning();
+            }
         }
     }
 
@@ -202,6 +427,14 @@ impl App {
     fn flash_warning(&mut self) {
         self.show_warning = true;
         self.warning_ticks = 6;
+        self.warning_colour = Color::Red;
+    }
+
+    fn flash_notice(&mut self, message: String) {
+        self.warning_message = Some(message);
+        self.show_warning = true;
+        self.warning_ticks = 12;
+        self.warning_colour = Color::Green;
     }
 
     fn tick_warning(&mut self) {
@@ -218,33 +451,131 @@ impl App {
     fn draw(&self, f: &mut Frame) {
         let k = self.game.get_max_attempts();
 
+        let stats_height = match &self.stats {
+            Some(summary) => summary.distribution.len().max(1) as u16 + 3,
+            None => 0,
+        };
+        let hints_height = if self.game.hints().is_empty() { 0 } else { 1 };
         let layout = Layout::vertical([
             Constraint::Length(k as u16 + 2),
+            Constraint::Length(keyboard_rows(self.game.get_word().alphabet()).len() as u16),
+            Constraint::Length(hints_height),
             Constraint::Length(1),
             Constraint::Length(2),
+            Constraint::Length(stats_height),
         ])
         .split(f.area());
 
         self.draw_board(f, layout[0]);
-        self.draw_status(f, layout[1]);
-        self.draw_info(f, layout[2]);
+        self.draw_keyboard(f, layout[1]);
+        self.draw_hints(f, layout[2]);
+        self.draw_status(f, layout[3]);
+        self.draw_info(f, layout[4]);
+        if let Some(summary) = &self.stats {
+            self.draw_stats(f, layout[5], summary);
+        }
+    }
+
+    fn draw_hints(&self, f: &mut Frame, area: Rect) {
+        let hints: Vec<String> = self.game.hints().iter().map(|h| h.to_string()).collect();
+        let text = format!(
+            "Hints (cost {}): {}",
+            self.game.hint_cost(),
+            hints.join(" · ")
+        );
+        f.render_widget(Paragraph::new(text).style(Style::default().fg(Color::Yellow)), area);
+    }
+
+    fn draw_keyboard(&self, f: &mut Frame, area: Rect) {
+        let lines = keyboard_lines(&self.colour_config, self.game.get_word().alphabet(), &self.game.letter_states());
+        f.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn draw_stats(&self, f: &mut Frame, area: Rect, summary: &Summary) {
+        let mut lines = vec![Line::from(summary.to_string())];
+        let widest = summary.distribution.iter().copied().max().unwrap_or(0).max(1);
+        let this_game = match self.game.get_state() {
+            GameState::Over(GameResult::Won) => Some(self.game.get_attempts() as usize),
+            _ => None,
+        };
+        for (i, count) in summary.distribution.iter().enumerate() {
+            let width = (*count as usize * 20).div_ceil(widest as usize);
+            let style = if this_game == Some(i + 1) {
+                self.colour_config[0].to_style().fg(Color::Black)
+            } else {
+                self.colour_config[2].to_style().fg(Color::Black)
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("{:>2} ", i + 1)),
+                Span::styled(format!("{:<width$}", count, width = width.max(2)), style),
+            ]));
+        }
+        let title = match self.game.get_mode() {
+            GameMode::Classic => format!("Statistics ({} letters)", self.game.get_word_length()),
+            mode => format!("{} statistics ({} letters)", mode.name(), self.game.get_word_length()),
+        };
+        f.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title)),
+            area,
+        );
     }
 
     fn draw_board(&self, f: &mut Frame, area: Rect) {
         let mut lines = Vec::new();
+        let lies = self.game.lies();
+        let counts = match self.game.get_mode() {
+            GameMode::Mastermind => self.game.counts(),
+            _ => Vec::new(),
+        };
 
-        for row in &self.board {
+        for (r, row) in self.board().iter().enumerate() {
             let mut spans = Vec::new();
-            for cell in row {
-                let (ch, style) = self.render_cell(cell);
-                spans.push(Span::styled(format!("[{}]", ch), style));
+            for (c, cell) in row.iter().enumerate() {
+                let (ch, style) = render_cell(&self.colour_config, cell);
+                let (open, close) = if self.mark_cursor == Some((r, c)) {
+                    ('>', '<')
+                } else if lies.is_some_and(|lies| lies.get(r).is_some_and(|l| l.contains(&c))) {
+                    ('!', '!')
+                } else if self.suspects.contains(&(r, c)) {
+                    ('(', ')')
+                } else {
+                    ('[', ']')
+                };
+                spans.push(Span::styled(format!("{}{}{}", open, ch, close), style));
+            }
+            match counts.get(r) {
+                Some(count) => {
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::styled(
+                        format!("{:>2}", count.matched),
+                        self.colour_config[0].to_style().fg(Color::Black),
+                    ));
+                    spans.push(Span::styled(
+                        format!("{:>2}", count.present),
+                        self.colour_config[1].to_style().fg(Color::Black),
+                    ));
+                }
+                None => spans.push(Span::styled("[↵]", Style::default().fg(Color::DarkGray))),
             }
-            spans.push(Span::styled("[↵]", Style::default().fg(Color::DarkGray)));
             lines.push(Line::from(spans));
         }
 
+        let mut title = String::from(self.game.get_mode().name());
+        if self.game.get_difficulty() == GameDifficulty::Hard {
+            title.push_str(" (Hard)");
+        }
+        if let Some(blitz) = &self.blitz {
+            title.push_str(&format!(" Blitz - puzzle {}", blitz.solved() + 1));
+        } else if let Some(daily) = self.game.get_daily() {
+            title.push_str(&format!(" #{} ({})", daily.number(), daily.date()));
+        } else if let Some(seed) = self.game.get_seed() {
+            title.push_str(&format!(" - seed {}", seed));
+        }
+        if let Some(remaining) = self.game.remaining() {
+            title.push_str(&format!(" ⏱ {}", clock(remaining)));
+        }
         let p = Paragraph::new(lines)
-            .block(Block::default().borders(Borders::ALL).title("Wordle"));
+            .block(Block::default().borders(Borders::ALL).title(title));
 
         f.render_widget(p, area);
 
@@ -252,40 +583,48 @@ impl App {
         match self.game.get_state() {
             GameState::InProgress => {
                 let row = self.game.get_attempts();
-                let x = area.x + 1 + (self.input.len() as u16) * 3 + 1;
+                let x = area.x + 1 + (self.input.chars().count() as u16) * 3 + 1;
                 let y = area.y + 1 + row as u16;
-                f.set_cursor_position(Position { x: x, y: y });
+                f.set_cursor_position(Position { x, y });
             }
             GameState::Over(_) => {}
             _ => {}
         }
     }
 
-    fn render_cell(&self, cell: &Cell) -> (char, Style) {
-        match cell {
-            Cell::Empty => (' ', Style::default().fg(Color::DarkGray)),
-            Cell::Input(c) => (*c, Style::default().fg(Color::White)),
-            Cell::Result(c, state) => {
-                let style = match state {
-                    LetterState::Matched =>
-                        self.colour_config[0].to_style().fg(Color::Black),
-                    LetterState::Present =>
-                        self.colour_config[1].to_style().fg(Color::Black),
-                    LetterState::Absent =>
-                        self.colour_config[2].to_style().fg(Color::Black),
-                };
-                (*c, style)
-            }
-        }
-    }
     fn draw_status(&self, f: &mut Frame, area: Rect) {
-        let (text, style) = if let Some(msg) = self.game_over_message.clone() {
-            (msg, Style::default().fg(Color::Green))
+        let (text, style) = if self.hint_menu {
+            (
+                format!(
+                    "Hint: 1 reveal a letter ({}), 2 count answers ({}), 3 suggest a word ({}), Esc cancel",
+                    HintKind::RevealLetter.cost(),
+                    HintKind::CandidatesLeft.cost(),
+                    HintKind::SuggestWord.cost()
+                ),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if self.mark_cursor.is_some() {
+            (
+                String::from("Mark suspected lies: arrows move, Space toggles, Tab when done"),
+                Style::default().fg(Color::Yellow),
+            )
+        } else if let Some(saved) = &self.pending_resume {
+            (
+                format!(
+                    "Resume saved game ({} letters, {}/{})? [y/n]",
+                    saved.get_word_length(),
+                    saved.get_attempts(),
+                    saved.get_max_attempts()
+                ),
+                Style::default().fg(Color::Yellow),
+            )
         } else if self.show_warning {
             (
                 self.warning_message.clone().unwrap(),
-                Style::default().fg(Color::Red),
+                Style::default().fg(self.warning_colour),
             )
+        } else if let Some(msg) = self.game_over_message.clone() {
+            (msg, Style::default().fg(Color::Green))
         } else {
             (String::from(""), Style::default())
         };
@@ -295,9 +634,56 @@ impl App {
     }
     fn draw_info(&self, f: &mut Frame, area: Rect) {
         let style = Style::new().fg(Color::Green);
-        f.render_widget(
-            Paragraph::new("⌃C - quit game\n^G - give up").style(style),
-            area,
-        );
+        let mut text = String::from(match self.game.get_state() {
+            GameState::Over(_) => "^C - quit game\n^Y - copy result, ^S - save result to file",
+            _ => "^C - save and quit\n^G - give up, ^T or ? - hint",
+        });
+        if matches!(self.game.get_mode(), GameMode::Fibble { .. }) {
+            text.push_str(", Tab - mark lies");
+        }
+        f.render_widget(Paragraph::new(text).style(style), area);
     }
 }
+
+pub(super) fn render_cell(colour_config: &ColourConfig, cell: &Cell) -> (char, Style) {
+    match cell {
+        Cell::Empty => (' ', Style::default().fg(Color::DarkGray)),
+        Cell::Input(c) => (*c, Style::default().fg(Color::White)),
+        Cell::Played(c) => (*c, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        Cell::Result(c, state) => {
+            let style = match state {
+                LetterState::Matched =>
+                    colour_config[0].to_style().fg(Color::Black),
+                LetterState::Present =>
+                    colour_config[1].to_style().fg(Color::Black),
+                LetterState::Absent =>
+                    colour_config[2].to_style().fg(Color::Black),
+            };
+            (*c, style)
+        }
+    }
+}
+
+/// The keyboard rows with every guessed letter coloured by its state.
+pub(super) fn keyboard_lines(
+    colour_config: &ColourConfig,
+    alphabet: &Alphabet,
+    states: &HashMap<char, LetterState>,
+) -> Vec<Line<'static>> {
+    keyboard_rows(alphabet)
+        .iter()
+        .map(|(row, indent)| {
+            let mut spans = vec![Span::raw(" ".repeat(indent * 3 / 2 + 1))];
+            for c in row.chars() {
+                let style = match states.get(&c) {
+                    Some(LetterState::Matched) => colour_config[0].to_style().fg(Color::Black),
+                    Some(LetterState::Present) => colour_config[1].to_style().fg(Color::Black),
+                    Some(LetterState::Absent) => colour_config[2].to_style().fg(Color::DarkGray),
+                    None => Style::default().fg(Color::White),
+                };
+                spans.push(Span::styled(format!(" {} ", to_upper(c)), style));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+/// The letters of a language and how typed or listed text maps onto them.
+/// Words are stored folded: lower case, with accents removed when the
+/// alphabet ignores them, so comparing letters is comparing `char`s.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Alphabet {
+    /// Every letter, in the order the keyboard lists the ones QWERTY lacks.
+    letters: Vec<char>,
+    /// Lower-case forms that `char::to_lowercase` gets wrong for the
+    /// language, e.g. Turkish `I` → `ı`.
+    case: HashMap<char, char>,
+    /// Letters that count as another letter, e.g. `é` → `e`. Empty unless
+    /// the language matches accents loosely.
+    accents: HashMap<char, char>,
+}
+
+impl Default for Alphabet {
+    /// The English alphabet, a-z.
+    fn default() -> Self {
+        Alphabet {
+            letters: ('a'..='z').collect(),
+            case: HashMap::new(),
+            accents: HashMap::new(),
+        }
+    }
+}
+
+impl Alphabet {
+    /// Checks that every letter is a single lower-case character that is
+    /// listed once, and that case and accent mappings lead to letters.
+    /// Letters written as a base and a combining accent are composed first.
+    pub fn new(letters: &str, case: HashMap<char, char>, accents: HashMap<char, char>) -> Result<Self, Vec<String>> {
+        let mut problems = Vec::new();
+        let mut list: Vec<char> = Vec::new();
+        for c in compose(letters).chars().filter(|c| !c.is_whitespace()) {
+            if !c.is_alphabetic() || c.is_uppercase() {
+                problems.push(format!("letter '{}' must be a lower-case letter", c));
+            } else if list.contains(&c) {
+                problems.push(format!("letter '{}' is listed more than once", c));
+            } else {
+                list.push(c);
+            }
+        }
+        if list.is_empty() && problems.is_empty() {
+            problems.push(String::from("there are no letters"));
+        }
+        for (from, to) in case.iter().chain(accents.iter()) {
+            if !list.contains(to) {
+                problems.push(format!("'{}' maps to '{}', which is not a letter", from, to));
+            }
+        }
+        for from in accents.keys() {
+            if list.contains(from) {
+                problems.push(format!("'{}' is a letter, it can't also be an accent of another", from));
+            }
+        }
+        if !problems.is_empty() {
+            return Err(problems);
+        }
+        Ok(Alphabet {
+            letters: list,
+            case,
+            accents,
+        })
+    }
+    pub fn letters(&self) -> &[char] {
+        &self.letters
+    }
+    /// The letter `c` is typed as, or `None` if it isn't one.
+    pub fn fold_char(&self, c: char) -> Option<char> {
+        let c = match self.case.get(&c) {
+            Some(&lower) => lower,
+            None => single(c.to_lowercase()).unwrap_or(c),
+        };
+        let c = self.accents.get(&c).copied().unwrap_or(c);
+        self.letters.contains(&c).then_some(c)
+    }
+    /// `word` folded letter by letter, or the first character that isn't
+    /// in the alphabet. Decomposed letters (`e` followed by a combining
+    /// acute) fold like their precomposed form.
+    pub fn fold(&self, word: &str) -> Result<String, char> {
+        compose(word.trim()).chars().map(|c| self.fold_char(c).ok_or(c)).collect()
+    }
+}
+
+/// Precomposed letters by combining mark, as pairs of base and composed
+/// letter. Covers Latin-1 and Latin Extended-A, the letters of the European
+/// languages packs are written in.
+const COMPOSITIONS: [(char, &str); 13] = [
+    ('\u{0300}', "AÀEÈIÌOÒUÙaàeèiìoòuù"),
+    ('\u{0301}', "AÁEÉIÍOÓUÚYÝaáeéiíoóuúyýCĆcćLĹlĺNŃnńRŔrŕSŚsśZŹzź"),
+    ('\u{0302}', "AÂEÊIÎOÔUÛaâeêiîoôuûCĈcĉGĜgĝHĤhĥJĴjĵSŜsŝWŴwŵYŶyŷ"),
+    ('\u{0303}', "AÃNÑOÕaãnñoõIĨiĩUŨuũ"),
+    ('\u{0304}', "AĀaāEĒeēIĪiīOŌoōUŪuū"),
+    ('\u{0306}', "AĂaăEĔeĕGĞgğIĬiĭOŎoŏUŬuŭ"),
+    ('\u{0307}', "CĊcċEĖeėGĠgġIİZŻzż"),
+    ('\u{0308}', "AÄEËIÏOÖUÜaäeëiïoöuüyÿYŸ"),
+    ('\u{030a}', "AÅaåUŮuů"),
+    ('\u{030b}', "OŐoőUŰuű"),
+    ('\u{030c}', "CČcčDĎdďEĚeěLĽlľNŇnňRŘrřSŠsšTŤtťZŽzž"),
+    ('\u{0327}', "CÇcçGĢgģKĶkķLĻlļNŅnņRŖrŗSŞsşTŢtţ"),
+    ('\u{0328}', "AĄaąEĘeęIĮiįUŲuų"),
+];
+
+/// `text` with every letter that `COMPOSITIONS` knows merged with the
+/// combining mark after it, the way NFC does. Other marks are kept, so they
+/// still show up as characters outside the alphabet.
+fn compose(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(mut c) = chars.next() {
+        while let Some(&mark) = chars.peek()
+            && let Some(composed) = compose_pair(c, mark)
+        {
+            c = composed;
+            chars.next();
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn compose_pair(base: char, mark: char) -> Option<char> {
+    let (_, pairs) = COMPOSITIONS.iter().find(|(m, _)| *m == mark)?;
+    let mut chars = pairs.chars();
+    while let (Some(b), Some(composed)) = (chars.next(), chars.next()) {
+        if b == base {
+            return Some(composed);
+        }
+    }
+    None
+}
+
+/// `c` in upper case for display, kept as is when that takes more than one
+/// character (`ß` would become `SS` and no longer fill a single tile).
+pub fn to_upper(c: char) -> char {
+    single(c.to_uppercase()).unwrap_or(c)
+}
+
+pub(crate) fn single(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn french() -> Alphabet {
+        let accents = [('é', 'e'), ('è', 'e'), ('ç', 'c')].into();
+        Alphabet::new("abcdefghijklmnopqrstuvwxyz", HashMap::new(), accents).unwrap()
+    }
+
+    fn spanish() -> Alphabet {
+        Alphabet::new("abcdefghijklmnñopqrstuvwxyz", HashMap::new(), HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn folds_case() {
+        assert_eq!(Alphabet::default().fold(" CrAne "), Ok(String::from("crane")));
+        let turkish = Alphabet::new("abcçdefgğhıijklmnoöprsştuüvyz", [('I', 'ı')].into(), HashMap::new()).unwrap();
+        assert_eq!(turkish.fold("KIZ"), Ok(String::from("kız")));
+    }
+
+    #[test]
+    fn folds_accents() {
+        assert_eq!(french().fold("Été"), Ok(String::from("ete")));
+        assert_eq!(french().fold("leçon"), Ok(String::from("lecon")));
+    }
+
+    #[test]
+    fn folds_decomposed_letters() {
+        assert_eq!(french().fold("e\u{301}te\u{301}"), Ok(String::from("ete")));
+        assert_eq!(spanish().fold("NIN\u{303}O"), Ok(String::from("niño")));
+        let nfd = Alphabet::new("abcdefghijklmnn\u{303}opqrstuvwxyz", HashMap::new(), HashMap::new()).unwrap();
+        assert_eq!(nfd, spanish());
+    }
+
+    #[test]
+    fn rejects_letters_outside_the_alphabet() {
+        assert_eq!(Alphabet::default().fold("niño"), Err('ñ'));
+        assert_eq!(spanish().fold("sen\u{301}or"), Err('ń'));
+        assert_eq!(spanish().fold("so\u{31b}l"), Err('\u{31b}'));
+        assert_eq!(french().fold("crane1"), Err('1'));
+    }
+
+    #[test]
+    fn rejects_bad_alphabets() {
+        let problems = Alphabet::new("abcA", HashMap::new(), [('é', 'e')].into()).unwrap_err();
+        assert_eq!(
+            problems,
+            ["letter 'A' must be a lower-case letter", "'é' maps to 'e', which is not a letter"]
+        );
+        assert!(Alphabet::new("aba", HashMap::new(), HashMap::new()).is_err());
+    }
+}
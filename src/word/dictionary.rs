@@ -0,0 +1,283 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Identifies the compact dictionary format, followed by a `u32` version.
+const MAGIC: &[u8; 8] = b"WRDLDICT";
+const VERSION: u32 = 1;
+/// Magic and version, then the number of buckets.
+const HEADER_LEN: usize = 16;
+/// Byte length, word count and data offset of a bucket, each a `u32`.
+const BUCKET_LEN: usize = 12;
+
+/// The set of words that may be guessed.
+///
+/// Words are kept in the compact format written by `encode`: words grouped
+/// by their length in bytes, each group one sorted run of fixed-size
+/// records. Lookups binary-search the run of the right length, and nothing
+/// is decoded up front, so the dictionary compiled into the binary is used
+/// in place without copying it to the heap. Words added later (such as a
+/// pack's) are kept in a set alongside.
+///
+/// File layout, integers little-endian:
+///
+/// ```text
+/// "WRDLDICT" u32 version  u32 bucket count
+/// per bucket: u32 byte length  u32 word count  u32 offset of its first word
+/// the words of every bucket, sorted, without separators
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    bytes: Cow<'static, [u8]>,
+    buckets: Vec<Bucket>,
+    extra: HashSet<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    byte_len: usize,
+    count: usize,
+    offset: usize,
+}
+
+impl Dictionary {
+    /// Reads the compact format, checking only its header and layout.
+    pub fn from_bytes(bytes: Cow<'static, [u8]>) -> Result<Self, String> {
+        let read_u32 = |at: usize| -> Result<usize, String> {
+            bytes
+                .get(at..at + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+                .ok_or_else(|| String::from("file is truncated"))
+        };
+        if bytes.get(..MAGIC.len()) != Some(&MAGIC[..]) {
+            return Err(String::from("not a compact dictionary"));
+        }
+        let version = read_u32(MAGIC.len())?;
+        if version != VERSION as usize {
+            return Err(format!("unsupported compact dictionary version {}", version));
+        }
+        let bucket_count = read_u32(MAGIC.len() + 4)?;
+        let mut buckets = Vec::with_capacity(bucket_count.min(256));
+        for i in 0..bucket_count {
+            let at = HEADER_LEN + i * BUCKET_LEN;
+            let bucket = Bucket {
+                byte_len: read_u32(at)?,
+                count: read_u32(at + 4)?,
+                offset: read_u32(at + 8)?,
+            };
+            let end = bucket
+                .byte_len
+                .checked_mul(bucket.count)
+                .and_then(|size| size.checked_add(bucket.offset));
+            if bucket.byte_len == 0 || end.is_none_or(|end| end > bytes.len()) {
+                return Err(format!("bucket {} is out of bounds", i));
+            }
+            buckets.push(bucket);
+        }
+        Ok(Dictionary {
+            bytes,
+            buckets,
+            extra: HashSet::new(),
+        })
+    }
+    /// Reads a dictionary JSON list (an object of word arrays keyed by
+    /// length) and packs it.
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let words = parse_json(json)?;
+        Ok(Self::from_bytes(Cow::Owned(encode(words.iter().map(String::as_str))))?)
+    }
+    /// Reads a file in either format, told apart by the compact format's
+    /// magic bytes.
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let context = |err: &dyn std::fmt::Display| format!("{}: {}", path.display(), err);
+        let bytes = fs::read(path).map_err(|err| context(&err))?;
+        if bytes.starts_with(MAGIC) {
+            return Ok(Self::from_bytes(Cow::Owned(bytes)).map_err(|err| context(&err))?);
+        }
+        let json = String::from_utf8(bytes).map_err(|err| context(&err))?;
+        Self::from_json(&json).map_err(|err| context(&err).into())
+    }
+    pub fn contains(&self, word: &str) -> bool {
+        self.packed_contains(word) || self.extra.contains(word)
+    }
+    /// Every word, the packed ones first, shortest (in bytes) and in sorted
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.buckets
+            .iter()
+            .flat_map(|bucket| {
+                self.bytes[bucket.offset..bucket.offset + bucket.byte_len * bucket.count]
+                    .chunks_exact(bucket.byte_len)
+                    .filter_map(|word| std::str::from_utf8(word).ok())
+            })
+            .chain(self.extra.iter().filter(|w| !self.packed_contains(w)).map(String::as_str))
+    }
+    pub fn len(&self) -> usize {
+        self.buckets.iter().map(|b| b.count).sum::<usize>()
+            + self.extra.iter().filter(|w| !self.packed_contains(w)).count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Adds words on top of the packed ones.
+    pub fn extend(&mut self, words: impl IntoIterator<Item = String>) {
+        self.extra.extend(words);
+    }
+    pub fn clear(&mut self) {
+        *self = Dictionary::default();
+    }
+    fn packed_contains(&self, word: &str) -> bool {
+        let Some(bucket) = self.buckets.iter().find(|b| b.byte_len == word.len()) else {
+            return false;
+        };
+        let words = &self.bytes[bucket.offset..bucket.offset + bucket.byte_len * bucket.count];
+        let (mut low, mut high) = (0, bucket.count);
+        while low < high {
+            let mid = (low + high) / 2;
+            let at = mid * bucket.byte_len;
+            match words[at..at + bucket.byte_len].cmp(word.as_bytes()) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return true,
+            }
+        }
+        false
+    }
+}
+
+/// Packs `words` into the compact format. Duplicates are dropped.
+pub fn encode<'a>(words: impl IntoIterator<Item = &'a str>) -> Vec<u8> {
+    let mut buckets: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for word in words.into_iter().filter(|w| !w.is_empty()) {
+        buckets.entry(word.len()).or_default().push(word);
+    }
+    for words in buckets.values_mut() {
+        words.sort_unstable();
+        words.dedup();
+    }
+    let mut out = Vec::new();
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&VERSION.to_le_bytes());
+    out.extend_from_slice(&(buckets.len() as u32).to_le_bytes());
+    let mut offset = HEADER_LEN + buckets.len() * BUCKET_LEN;
+    for (byte_len, words) in &buckets {
+        out.extend_from_slice(&(*byte_len as u32).to_le_bytes());
+        out.extend_from_slice(&(words.len() as u32).to_le_bytes());
+        out.extend_from_slice(&(offset as u32).to_le_bytes());
+        offset += byte_len * words.len();
+    }
+    for word in buckets.values().flatten() {
+        out.extend_from_slice(word.as_bytes());
+    }
+    out
+}
+
+/// Every word of a dictionary JSON list: an object of word arrays keyed by
+/// length. Anything else in it is ignored.
+pub fn parse_json(json: &str) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
+    let v: serde_json::Value = serde_json::from_str(json)?;
+
+    let mut set = HashSet::new();
+
+    if let serde_json::Value::Object(map) = v {
+        for (_len, words) in map {
+            if let serde_json::Value::Array(arr) = words {
+                for word in arr {
+                    if let serde_json::Value::String(s) = word {
+                        set.insert(s);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 7] = ["crane", "slate", "crane", "größe", "ox", "strength", "ñandú"];
+
+    fn packed() -> Vec<u8> {
+        encode(WORDS)
+    }
+
+    #[test]
+    fn round_trips_through_the_compact_format() {
+        let dictionary = Dictionary::from_bytes(Cow::Owned(packed())).unwrap();
+        let mut expected: Vec<&str> = WORDS.to_vec();
+        expected.sort_by_key(|w| (w.len(), *w));
+        expected.dedup();
+        assert_eq!(dictionary.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(dictionary.len(), expected.len());
+        for word in WORDS {
+            assert!(dictionary.contains(word), "{}", word);
+        }
+        for word in ["", "crank", "cran", "cranes", "grosse", "o"] {
+            assert!(!dictionary.contains(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn extra_words_are_counted_once() {
+        let mut dictionary = Dictionary::from_bytes(Cow::Owned(packed())).unwrap();
+        dictionary.extend(["regex", "crane"].map(String::from));
+        assert!(dictionary.contains("regex"));
+        assert_eq!(dictionary.len(), 7);
+        assert_eq!(dictionary.iter().filter(|&w| w == "crane").count(), 1);
+    }
+
+    #[test]
+    fn corrupted_header_is_rejected() {
+        let load = |bytes: Vec<u8>| Dictionary::from_bytes(Cow::Owned(bytes));
+        let mut bytes = packed();
+        bytes[0] = b'X';
+        assert_eq!(load(bytes).unwrap_err(), "not a compact dictionary");
+
+        let mut bytes = packed();
+        bytes[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&2u32.to_le_bytes());
+        assert_eq!(load(bytes).unwrap_err(), "unsupported compact dictionary version 2");
+
+        let mut bytes = packed();
+        bytes[MAGIC.len() + 4..HEADER_LEN].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(load(bytes).is_err());
+
+        // A bucket whose words run past the end of the file.
+        let mut bytes = packed();
+        let count = HEADER_LEN + 4;
+        bytes[count..count + 4].copy_from_slice(&1000u32.to_le_bytes());
+        assert_eq!(load(bytes).unwrap_err(), "bucket 0 is out of bounds");
+    }
+
+    #[test]
+    fn truncated_file_is_rejected() {
+        let bytes = packed();
+        for len in [0, 4, MAGIC.len() + 2, HEADER_LEN - 1, HEADER_LEN + 5, bytes.len() - 1] {
+            assert!(
+                Dictionary::from_bytes(Cow::Owned(bytes[..len].to_vec())).is_err(),
+                "{} of {} bytes",
+                len,
+                bytes.len()
+            );
+        }
+    }
+
+    #[test]
+    fn bundled_dictionary_agrees_with_its_json() {
+        let json = fs::read_to_string("assets/word/dictionary.json").unwrap();
+        let words = parse_json(&json).unwrap();
+        let dictionary = Dictionary::from_file(Path::new("assets/word/dictionary.bin")).unwrap();
+        assert_eq!(dictionary.len(), words.len());
+        for word in &words {
+            assert!(dictionary.contains(word), "{} is missing", word);
+            let mut miss = word.clone();
+            miss.push('q');
+            assert_eq!(dictionary.contains(&miss), words.contains(&miss), "{}", miss);
+        }
+        assert!(dictionary.iter().all(|word| words.contains(word)));
+    }
+}
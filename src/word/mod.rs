@@ -1 +1,7 @@
-pub mod word;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod word;
+pub mod query;
+pub mod tiers;
+pub mod pack;
+pub mod alphabet;
+pub mod dictionary;
\ No newline at end of file
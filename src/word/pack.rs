@@ -0,0 +1,365 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    game::judge::MAX_PATTERN_LEN,
+    word::alphabet::{self, Alphabet},
+};
+
+/// Language of the built-in lists. Packs in this language may also be
+/// guessed with any word of the built-in dictionary.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// A themed answer list, loaded from a TOML manifest in one of
+/// `paths::pack_dirs()`:
+///
+/// ```toml
+/// name = "Programming"
+/// language = "en"                          # optional, default "en"
+/// alphabet = "abcdefghijklmnopqrstuvwxyz"  # optional, default a-z
+///
+/// [answers]                                # keyed by word length
+/// 5 = ["array", "class"]
+///
+/// [guesses]                                # optional extra allowed guesses
+/// 5 = ["regex"]
+/// ```
+///
+/// `alphabet` may instead be a table, for letters that need more than
+/// `to_lowercase` or that should match loosely:
+///
+/// ```toml
+/// [alphabet]
+/// letters = "abcdefghijklmnopqrstuvwxyzñ"
+/// accents = { "á" = "a", "é" = "e" }       # optional, typed and judged as "a", "e"
+/// case = { "I" = "ı" }                     # optional, lower case of "I"
+/// ```
+///
+/// Words are folded through the alphabet, so "Größe" is stored as "größe".
+/// The pack's id is the manifest's file name without `.toml`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pack {
+    pub id: String,
+    pub name: String,
+    pub language: String,
+    pub alphabet: Alphabet,
+    /// Indexed by word length, like `Word::answer`.
+    pub answers: Vec<Vec<String>>,
+    pub guesses: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum PackError {
+    Io { path: PathBuf, error: std::io::Error },
+    Toml { path: PathBuf, error: toml::de::Error },
+    /// The manifest is valid TOML but not a valid pack, one message per problem.
+    Invalid { path: PathBuf, problems: Vec<String> },
+    NotFound(String),
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            Self::Toml { path, error } => write!(f, "{}: not a valid manifest: {}", path.display(), error),
+            Self::Invalid { path, problems } => {
+                write!(f, "{}: invalid pack:", path.display())?;
+                for problem in problems {
+                    write!(f, "\n  {}", problem)?;
+                }
+                Ok(())
+            }
+            Self::NotFound(id) => write!(
+                f,
+                "No word pack named '{}'. Run 'wordle packs' to list the installed packs.",
+                id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+impl fmt::Display for Pack {
+    /// e.g. "Programming (en, 5-6 letters, 48 answers)".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lengths = self.lengths();
+        let first = lengths.first().copied().unwrap_or(0);
+        let last = lengths.last().copied().unwrap_or(0);
+        write!(f, "{} ({}, ", self.name, self.language)?;
+        if first == last {
+            write!(f, "{} letters", first)?;
+        } else {
+            write!(f, "{}-{} letters", first, last)?;
+        }
+        write!(f, ", {} answers", self.answers.iter().map(Vec::len).sum::<usize>())?;
+        if !self.guesses.is_empty() {
+            write!(f, ", {} extra guesses", self.guesses.len())?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl Pack {
+    /// Reads and validates the manifest at `path`.
+    pub fn load(path: &Path) -> Result<Self, PackError> {
+        let text = fs::read_to_string(path).map_err(|error| PackError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        Self::parse(path, &text)
+    }
+    /// Validates a manifest, reporting every problem at once. `path` names
+    /// the pack and its errors.
+    pub fn parse(path: &Path, text: &str) -> Result<Self, PackError> {
+        let table: toml::Table = toml::from_str(text).map_err(|error| PackError::Toml {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let mut problems = Vec::new();
+
+        let name = match table.get("name").and_then(toml::Value::as_str).map(str::trim) {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => {
+                problems.push(String::from("'name' must be a non-empty string"));
+                String::new()
+            }
+        };
+        let language = optional_string(&table, "language", DEFAULT_LANGUAGE, &mut problems).to_lowercase();
+        let alphabet = match table.get("alphabet") {
+            Some(value) => parse_alphabet(value).unwrap_or_else(|errors| {
+                problems.extend(errors.into_iter().map(|e| format!("alphabet: {}", e)));
+                Alphabet::default()
+            }),
+            None => Alphabet::default(),
+        };
+
+        let answers = match table.get("answers") {
+            Some(value) => word_lists("answers", value, &alphabet, &mut problems),
+            None => {
+                problems.push(String::from("'answers' is missing"));
+                Vec::new()
+            }
+        };
+        if table.contains_key("answers") && answers.iter().all(Vec::is_empty) && problems.is_empty() {
+            problems.push(String::from("'answers' has no words"));
+        }
+        let guesses = match table.get("guesses") {
+            Some(value) => word_lists("guesses", value, &alphabet, &mut problems)
+                .into_iter()
+                .flatten()
+                .collect(),
+            None => Vec::new(),
+        };
+        for key in table.keys() {
+            if !["name", "language", "alphabet", "answers", "guesses"].contains(&key.as_str()) {
+                problems.push(format!("unknown key '{}'", key));
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(PackError::Invalid {
+                path: path.to_path_buf(),
+                problems,
+            });
+        }
+        let id = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Ok(Pack {
+            id,
+            name,
+            language,
+            alphabet,
+            answers,
+            guesses,
+        })
+    }
+    /// Word lengths that have at least one answer, ascending.
+    pub fn lengths(&self) -> Vec<usize> {
+        (0..self.answers.len())
+            .filter(|&len| !self.answers[len].is_empty())
+            .collect()
+    }
+}
+
+/// Loads the pack `id` from the first of `paths::pack_dirs()` that has it.
+pub fn find(id: &str) -> Result<Pack, PackError> {
+    for dir in crate::paths::pack_dirs() {
+        let path = dir.join(format!("{}.toml", id));
+        if path.is_file() {
+            return Pack::load(&path);
+        }
+    }
+    Err(PackError::NotFound(id.to_string()))
+}
+
+/// Every installed pack sorted by id, including the ones that fail to load.
+/// When two directories have a pack with the same id, the earlier one wins
+/// as in `find`.
+pub fn discover() -> Vec<Result<Pack, PackError>> {
+    let mut manifests: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for dir in crate::paths::pack_dirs() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            if path.extension().is_some_and(|ext| ext == "toml")
+                && let Some(stem) = path.file_stem()
+            {
+                manifests.entry(stem.to_string_lossy().to_string()).or_insert(path);
+            }
+        }
+    }
+    manifests.values().map(|path| Pack::load(path)).collect()
+}
+
+fn optional_string(table: &toml::Table, key: &str, default: &str, problems: &mut Vec<String>) -> String {
+    match table.get(key) {
+        Some(toml::Value::String(s)) => s.trim().to_string(),
+        Some(_) => {
+            problems.push(format!("'{}' must be a string", key));
+            String::new()
+        }
+        None => default.to_string(),
+    }
+}
+
+/// `alphabet` as a string of letters or a table, see `Pack`.
+fn parse_alphabet(value: &toml::Value) -> Result<Alphabet, Vec<String>> {
+    let table = match value {
+        toml::Value::String(letters) => return Alphabet::new(letters, HashMap::new(), HashMap::new()),
+        toml::Value::Table(table) => table,
+        _ => return Err(vec![String::from("must be a string of letters or a table")]),
+    };
+    let mut problems = Vec::new();
+    let letters = match table.get("letters").and_then(toml::Value::as_str) {
+        Some(letters) => letters,
+        None => {
+            problems.push(String::from("'letters' must be a string"));
+            ""
+        }
+    };
+    let maps = ["case", "accents"].map(|key| {
+        let mut map = HashMap::new();
+        let Some(value) = table.get(key) else {
+            return map;
+        };
+        let Some(pairs) = value.as_table() else {
+            problems.push(format!("'{}' must be a table", key));
+            return map;
+        };
+        for (from, to) in pairs {
+            match (single_char(from), to.as_str().and_then(single_char)) {
+                (Some(from), Some(to)) => {
+                    map.insert(from, to);
+                }
+                _ => problems.push(format!("{}: '{}' = {} must map one character to one", key, from, to)),
+            }
+        }
+        map
+    });
+    for key in table.keys() {
+        if !["letters", "case", "accents"].contains(&key.as_str()) {
+            problems.push(format!("unknown key '{}'", key));
+        }
+    }
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+    let [case, accents] = maps;
+    Alphabet::new(letters, case, accents)
+}
+
+fn single_char(s: &str) -> Option<char> {
+    alphabet::single(s.chars())
+}
+
+/// Reads a table of word lists keyed by length, folding the words through
+/// `alphabet` and reporting any that don't fit their length or the alphabet.
+fn word_lists(key: &str, value: &toml::Value, alphabet: &Alphabet, problems: &mut Vec<String>) -> Vec<Vec<String>> {
+    let Some(lengths) = value.as_table() else {
+        problems.push(format!("'{}' must be a table of word lists keyed by length", key));
+        return Vec::new();
+    };
+    let mut lists: Vec<Vec<String>> = Vec::new();
+    let mut seen = HashSet::new();
+    for (length_key, words) in lengths {
+        let Some(length) = length_key.parse::<usize>().ok().filter(|&n| n > 0) else {
+            problems.push(format!("{}.{}: '{}' is not a word length", key, length_key, length_key));
+            continue;
+        };
+        if length > MAX_PATTERN_LEN {
+            problems.push(format!("{}.{}: words can have at most {} letters", key, length, MAX_PATTERN_LEN));
+            continue;
+        }
+        let Some(words) = words.as_array() else {
+            problems.push(format!("{}.{}: must be a list of words", key, length));
+            continue;
+        };
+        if lists.len() <= length {
+            lists.resize(length + 1, Vec::new());
+        }
+        for word in words {
+            let Some(word) = word.as_str() else {
+                problems.push(format!("{}.{}: {} is not a string", key, length, word));
+                continue;
+            };
+            let folded = match alphabet.fold(word) {
+                Ok(folded) => folded,
+                Err(c) => {
+                    problems.push(format!("{}.{}: '{}' contains '{}', which is not in the alphabet", key, length, word, c));
+                    continue;
+                }
+            };
+            let word = folded;
+            let count = word.chars().count();
+            if count != length {
+                problems.push(format!("{}.{}: '{}' has {} letters, not {}", key, length, word, count, length));
+            } else if !seen.insert(word.clone()) {
+                problems.push(format!("{}.{}: '{}' is listed more than once", key, length, word));
+            } else {
+                lists[length].push(word);
+            }
+        }
+    }
+    lists
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(text: &str) -> Vec<String> {
+        match Pack::parse(Path::new("test.toml"), text) {
+            Err(PackError::Invalid { problems, .. }) => problems,
+            other => panic!("expected an invalid pack, got {:?}", other.map(|pack| pack.id)),
+        }
+    }
+
+    #[test]
+    fn valid_pack_loads() {
+        let pack = Pack::parse(Path::new("test.toml"), "name = \"Test\"\n[answers]\n5 = [\"Crane\", \"slate\"]\n").unwrap();
+        assert_eq!(pack.id, "test");
+        assert_eq!(pack.answers[5], ["crane", "slate"]);
+        assert_eq!(pack.lengths(), [5]);
+    }
+
+    #[test]
+    fn words_too_long_to_judge_are_rejected() {
+        let long = "a".repeat(MAX_PATTERN_LEN + 1);
+        let text = format!("name = \"Test\"\n[answers]\n5 = [\"crane\"]\n{} = [\"{}\"]\n", long.len(), long);
+        assert_eq!(problems(&text), [format!("answers.{}: words can have at most {} letters", long.len(), MAX_PATTERN_LEN)]);
+        let text = format!("name = \"Test\"\n[answers]\n5 = [\"crane\"]\n[guesses]\n99999999999 = [\"{}\"]\n", long);
+        assert_eq!(problems(&text).len(), 1);
+    }
+
+    #[test]
+    fn wrong_length_and_foreign_letters_are_reported_together() {
+        let found = problems("name = \"Test\"\n[answers]\n5 = [\"crane\", \"cranes\", \"größe\"]\n");
+        assert_eq!(found.len(), 2, "{:?}", found);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::types::*;
+
+/// Constraints a word has to meet. Positions are 0-based. An empty query
+/// matches everything.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct WordQuery {
+    pub length: Option<usize>,
+    /// Letters known to be at a position.
+    pub known: Vec<(usize, char)>,
+    /// Letters that must appear, each with the positions it is known not to be at.
+    pub present: Vec<(char, Vec<usize>)>,
+    /// Letters that must not appear at all.
+    pub absent: Vec<char>,
+    pub min_counts: Vec<(char, usize)>,
+    pub max_counts: Vec<(char, usize)>,
+}
+
+impl WordQuery {
+    pub fn new(length: Option<usize>) -> Self {
+        WordQuery {
+            length,
+            ..Default::default()
+        }
+    }
+    pub fn known(mut self, position: usize, letter: char) -> Self {
+        if !self.known.contains(&(position, letter)) {
+            self.known.push((position, letter));
+        }
+        self
+    }
+    /// Requires `letter` somewhere, but not at any of `not_at`. Calling this
+    /// again for the same letter adds to the excluded positions.
+    pub fn present(mut self, letter: char, not_at: &[usize]) -> Self {
+        match self.present.iter_mut().find(|(c, _)| *c == letter) {
+            Some((_, positions)) => {
+                for p in not_at {
+                    if !positions.contains(p) {
+                        positions.push(*p);
+                    }
+                }
+            }
+            None => self.present.push((letter, not_at.to_vec())),
+        }
+        self
+    }
+    pub fn absent(mut self, letter: char) -> Self {
+        if !self.absent.contains(&letter) {
+            self.absent.push(letter);
+        }
+        self
+    }
+    /// Requires at least `count` copies of `letter`. Keeps the strictest bound.
+    pub fn min_count(mut self, letter: char, count: usize) -> Self {
+        match self.min_counts.iter_mut().find(|(c, _)| *c == letter) {
+            Some((_, n)) => *n = (*n).max(count),
+            None => self.min_counts.push((letter, count)),
+        }
+        self
+    }
+    /// Allows at most `count` copies of `letter`. Keeps the strictest bound.
+    pub fn max_count(mut self, letter: char, count: usize) -> Self {
+        match self.max_counts.iter_mut().find(|(c, _)| *c == letter) {
+            Some((_, n)) => *n = (*n).min(count),
+            None => self.max_counts.push((letter, count)),
+        }
+        self
+    }
+    /// Everything the feedback in `history` reveals. A word matches the
+    /// result exactly when it would have produced the same feedback for
+    /// every guess.
+    pub fn from_history(word_length: usize, history: &[GuessRecord]) -> Self {
+        let mut query = WordQuery::new(Some(word_length));
+        for record in history {
+            let letters: Vec<char> = record.word.chars().collect();
+            for (i, (&c, state)) in letters.iter().zip(record.result.iter()).enumerate() {
+                let revealed = letters
+                    .iter()
+                    .zip(record.result.iter())
+                    .filter(|(l, s)| **l == c && **s != LetterState::Absent)
+                    .count();
+                query = match state {
+                    LetterState::Matched => query.known(i, c),
+                    LetterState::Present => query.present(c, &[i]),
+                    LetterState::Absent if revealed > 0 => query.present(c, &[i]).max_count(c, revealed),
+                    LetterState::Absent => query.absent(c),
+                };
+                if revealed > 0 {
+                    query = query.min_count(c, revealed);
+                }
+            }
+        }
+        query
+    }
+    pub fn matches(&self, word: &str) -> bool {
+        let letters: Vec<char> = word.chars().collect();
+        if self.length.is_some_and(|len| letters.len() != len) {
+            return false;
+        }
+        let count = |letter: char| letters.iter().filter(|&&c| c == letter).count();
+        self.known
+            .iter()
+            .all(|&(i, c)| letters.get(i) == Some(&c))
+            && self.present.iter().all(|(c, not_at)| {
+                letters.contains(c) && not_at.iter().all(|&i| letters.get(i) != Some(c))
+            })
+            && self.absent.iter().all(|c| !letters.contains(c))
+            && self.min_counts.iter().all(|&(c, n)| count(c) >= n)
+            && self.max_counts.iter().all(|&(c, n)| count(c) <= n)
+    }
+}
+
+/// Which list a query searches.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WordSource {
+    /// Possible answers, most common first.
+    Answers,
+    /// Every allowed guess, alphabetical.
+    Dictionary,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::judge;
+
+    fn history(answer: &str, guesses: &[&str]) -> Vec<GuessRecord> {
+        guesses
+            .iter()
+            .map(|&guess| GuessRecord {
+                word: guess.to_string(),
+                result: judge::judge(guess, answer).unwrap(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn present_and_absent_copies_cap_the_count() {
+        // One e of "speed" is present, the other absent: "abide" has exactly one.
+        let query = WordQuery::from_history(5, &history("abide", &["speed"]));
+        assert!(query.matches("abide"));
+        assert!(query.min_counts.contains(&('e', 1)));
+        assert!(query.max_counts.contains(&('e', 1)));
+        assert!(!query.absent.contains(&'e'));
+        assert!(!query.matches("emcee"), "two copies of e");
+        assert!(!query.matches("table"), "no d");
+        assert!(!query.matches("bleed"), "e at an excluded position");
+    }
+
+    #[test]
+    fn matched_and_absent_copies_cap_the_count() {
+        let query = WordQuery::from_history(5, &history("crane", &["eerie"]));
+        assert!(query.matches("crane"));
+        assert!(query.known.contains(&(4, 'e')));
+        assert!(query.max_counts.contains(&('e', 1)));
+        assert!(!query.matches("crepe"), "two copies of e");
+        assert!(!query.matches("clone"), "no r");
+    }
+
+    #[test]
+    fn answer_always_fits_its_own_history() {
+        let cases = [
+            ("abide", &["speed", "eerie", "ideal"][..]),
+            ("erase", &["speed", "geese"][..]),
+            ("llama", &["allow", "hello", "mamma"][..]),
+            ("kebab", &["abbey", "babes"][..]),
+            ("there", &["eerie", "three"][..]),
+        ];
+        for (answer, guesses) in cases {
+            let query = WordQuery::from_history(5, &history(answer, guesses));
+            assert!(query.matches(answer), "{} against {:?}", answer, guesses);
+        }
+    }
+}
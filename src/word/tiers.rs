@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fs;
+
+use crate::types::GameDifficulty;
+
+/// Answers split by how common they are. Each list is indexed by word length
+/// like `Word::answer`, and every answer of the pool is in exactly one tier.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Tiers {
+    pub easy: Vec<Vec<String>>,
+    pub medium: Vec<Vec<String>>,
+    pub hard: Vec<Vec<String>>,
+}
+
+impl Tiers {
+    /// Ranks every length's answers by `ranks` and cuts them into thirds:
+    /// the most common third is Easy, the next Medium and the rest Hard.
+    /// Answers missing from `ranks` count as the least common and keep their
+    /// order.
+    pub fn split(answers: &[Vec<String>], ranks: &HashMap<String, usize>) -> Self {
+        let mut tiers = Tiers {
+            easy: vec![Vec::new(); answers.len()],
+            medium: vec![Vec::new(); answers.len()],
+            hard: vec![Vec::new(); answers.len()],
+        };
+        for (len, words) in answers.iter().enumerate() {
+            let mut ranked = words.clone();
+            ranked.sort_by_key(|w| ranks.get(w).copied().unwrap_or(usize::MAX));
+            let hard = ranked.split_off(ranked.len() * 2 / 3);
+            let medium = ranked.split_off(ranked.len() / 2);
+            tiers.easy[len] = ranked;
+            tiers.medium[len] = medium;
+            tiers.hard[len] = hard;
+        }
+        tiers
+    }
+    pub fn get(&self, difficulty: &GameDifficulty) -> &Vec<Vec<String>> {
+        match difficulty {
+            GameDifficulty::Easy => &self.easy,
+            GameDifficulty::Medium => &self.medium,
+            GameDifficulty::Hard => &self.hard,
+        }
+    }
+}
+
+/// Ranks answers by their place in their length's list. `answer.json` lists
+/// every length's words most common first, so this is the default ranking.
+pub fn list_ranks(answers: &[Vec<String>]) -> HashMap<String, usize> {
+    answers
+        .iter()
+        .flat_map(|words| words.iter().enumerate().map(|(rank, word)| (word.clone(), rank)))
+        .collect()
+}
+
+/// Reads a frequency list: one word per line, most common first. Blank lines
+/// and lines starting with `#` are skipped, and anything after the word on a
+/// line (such as a count) is ignored. Returns each word's rank.
+pub fn load_frequency(path: &str) -> Result<HashMap<String, usize>, Box<dyn std::error::Error>> {
+    let text = fs::read_to_string(path)?;
+    let mut ranks = HashMap::new();
+    for word in text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_whitespace().next())
+    {
+        let rank = ranks.len();
+        ranks.entry(word.to_lowercase()).or_insert(rank);
+    }
+    Ok(ranks)
+}
+
+/// Renders answer lists in the layout of `answer.json`: an object keyed by
+/// word length in ascending order, one word per line. Empty lengths are left
+/// out.
+pub fn answers_to_json(lists: &[Vec<String>]) -> String {
+    let entries: Vec<String> = lists
+        .iter()
+        .enumerate()
+        .filter(|(_, words)| !words.is_empty())
+        .map(|(len, words)| {
+            let words: Vec<String> = words
+                .iter()
+                .map(|w| format!("    {}", serde_json::Value::String(w.clone())))
+                .collect();
+            format!("  \"{}\": [\n{}\n  ]", len, words.join(",\n"))
+        })
+        .collect();
+    format!("{{\n{}\n}}", entries.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers() -> Vec<Vec<String>> {
+        let words = ["that", "with", "this", "from", "have", "they", "will", "been", "were"];
+        vec![Vec::new(), Vec::new(), Vec::new(), Vec::new(), words.iter().map(|w| w.to_string()).collect()]
+    }
+
+    #[test]
+    fn thirds_cover_every_answer_once() {
+        let answers = answers();
+        let tiers = Tiers::split(&answers, &list_ranks(&answers));
+        let mut all: Vec<&String> = tiers.easy[4].iter().chain(&tiers.medium[4]).chain(&tiers.hard[4]).collect();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), answers[4].len());
+        assert_eq!([tiers.easy[4].len(), tiers.medium[4].len(), tiers.hard[4].len()], [3, 3, 3]);
+    }
+
+    #[test]
+    fn first_listed_answers_are_easy() {
+        let answers = answers();
+        let tiers = Tiers::split(&answers, &list_ranks(&answers));
+        assert_eq!(tiers.easy[4], ["that", "with", "this"]);
+        assert_eq!(tiers.medium[4], ["from", "have", "they"]);
+        assert_eq!(tiers.hard[4], ["will", "been", "were"]);
+    }
+
+    #[test]
+    fn unranked_answers_are_least_common() {
+        let answers = answers();
+        let ranks: HashMap<String, usize> = [("were".to_string(), 0), ("been".to_string(), 1)].into();
+        let tiers = Tiers::split(&answers, &ranks);
+        assert_eq!(tiers.easy[4], ["were", "been", "that"]);
+        assert_eq!(tiers.hard[4], ["have", "they", "will"]);
+    }
+}
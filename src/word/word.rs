@@ -1,37 +1,258 @@
-use crate::types::GameDifficulty;
+use crate::types::{AnswerError, GameDifficulty};
+use crate::word::alphabet::Alphabet;
+use crate::word::dictionary::Dictionary;
+use crate::word::pack::{self, Pack};
+use crate::word::query::{WordQuery, WordSource};
 use rand::Rng;
-use serde_json::Value;
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 pub struct Word {
     pub answer: Vec<Vec<String>>,
-    pub dictionary: HashSet<String>,
+    pub dictionary: Dictionary,
+    /// Id of the pack whose answers these are, `None` for the built-in lists.
+    pack: Option<String>,
+    alphabet: Alphabet,
+    /// Where `answer` was read from, `None` when it was given as JSON.
+    source: Option<AnswerSource>,
 }
+
+/// The answer list a `Word` was loaded from, kept so a saved game can be
+/// resumed with the same list.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AnswerSource {
+    /// A list by name, see `read_list`.
+    List(String),
+    /// A file given on the command line.
+    File(String),
+}
+/// Where the bundled lists live in the source tree. Only maintenance commands
+/// read from here; the game itself uses the copies compiled into the binary.
+pub const ASSET_DIR: &str = "assets/word";
+pub const DICTIONARY_LIST: &str = "dictionary.json";
+/// `DICTIONARY_LIST` in the compact format, generated from it with
+/// `wordle build-dictionary`.
+pub const DICTIONARY_BIN: &str = "dictionary.bin";
+/// Every answer of every difficulty. The per-difficulty lists are generated
+/// from it with `wordle build-tiers`.
+pub const ANSWER_LIST: &str = "answer.json";
+
+/// Bundled lists by file name, used when no user-supplied file replaces them.
+const EMBEDDED: [(&str, &str); 4] = [
+    (ANSWER_LIST, include_str!("../../assets/word/answer.json")),
+    ("answer-easy.json", include_str!("../../assets/word/answer-easy.json")),
+    ("answer-medium.json", include_str!("../../assets/word/answer-medium.json")),
+    ("answer-hard.json", include_str!("../../assets/word/answer-hard.json")),
+];
+
+/// The bundled dictionary, used in place rather than decoded.
+const EMBEDDED_DICTIONARY: &[u8] = include_bytes!("../../assets/word/dictionary.bin");
+
+impl AnswerSource {
+    /// The list's JSON.
+    pub fn read(&self) -> Result<String, Box<dyn std::error::Error>> {
+        match self {
+            AnswerSource::List(name) => read_list(name),
+            AnswerSource::File(path) => fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err).into()),
+        }
+    }
+    /// Where `read` finds the list: a file path, or the list's name when the
+    /// bundled copy is used.
+    pub fn location(&self) -> String {
+        match self {
+            AnswerSource::List(name) => find_list(name).map_or_else(|| name.clone(), |path| path.display().to_string()),
+            AnswerSource::File(path) => path.clone(),
+        }
+    }
+}
+
+/// The answer list used for a difficulty.
+pub fn answer_list(difficulty: &GameDifficulty) -> &'static str {
+    match difficulty {
+        GameDifficulty::Easy => "answer-easy.json",
+        GameDifficulty::Medium => "answer-medium.json",
+        GameDifficulty::Hard => "answer-hard.json",
+    }
+}
+
+/// The answer list a game is drawn from. Daily puzzles use the full
+/// `ANSWER_LIST`, which `build-tiers` never rewrites, so everyone gets the
+/// same answer on any difficulty and regenerating the tiers can't change
+/// past or future puzzles.
+pub fn game_list(difficulty: &GameDifficulty, daily: bool) -> &'static str {
+    if daily { ANSWER_LIST } else { answer_list(difficulty) }
+}
+
+/// Reads the list called `name` from the first of `paths::word_dirs()` that
+/// has it, falling back to the bundled copy.
+pub fn read_list(name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    if let Some(path) = find_list(name) {
+        return fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err).into());
+    }
+    EMBEDDED
+        .iter()
+        .find(|(list, _)| *list == name)
+        .map(|(_, text)| text.to_string())
+        .ok_or_else(|| format!("Unknown word list '{}'.", name).into())
+}
+
+/// The user-supplied file for the list called `name`, if any.
+fn find_list(name: &str) -> Option<PathBuf> {
+    crate::paths::word_dirs().into_iter().map(|dir| dir.join(name)).find(|path| path.is_file())
+}
+
+/// Reads the dictionary from the first of `paths::word_dirs()` that has one,
+/// preferring `DICTIONARY_BIN` to `DICTIONARY_LIST`, falling back to the
+/// bundled copy.
+pub fn read_dictionary() -> Result<Dictionary, Box<dyn std::error::Error>> {
+    for dir in crate::paths::word_dirs() {
+        for name in [DICTIONARY_BIN, DICTIONARY_LIST] {
+            let path = dir.join(name);
+            if path.is_file() {
+                return Dictionary::from_file(&path);
+            }
+        }
+    }
+    Ok(Dictionary::from_bytes(Cow::Borrowed(EMBEDDED_DICTIONARY))?)
+}
+
 impl Word {
-    pub fn new(difficulty: &GameDifficulty) -> Self {
-        let answer_path = match difficulty { // Make answers for different difficulties in the future
-            GameDifficulty::Easy => "assets/word/answer.json",
-            GameDifficulty::Medium => "assets/word/answer.json",
-            GameDifficulty::Hard => "assets/word/answer.json",
-        };
-        let dictionary_path = "assets/word/dictionary.json";
-        let answer: Vec<Vec<String>> =
-            load_answer(answer_path).expect(format!("Failed to load {}", answer_path).as_str());
-        let dictionary: HashSet<String> =
-            load_dictionary(dictionary_path).expect(format!("Failed to load {}", dictionary_path).as_str());
-        return Word { answer, dictionary };
+    pub fn new(difficulty: &GameDifficulty) -> Result<Self, Box<dyn std::error::Error>> {
+        Word::from_source(&AnswerSource::List(answer_list(difficulty).to_string()))
+    }
+    /// Loads the answers from `source` and the default dictionary. Errors name
+    /// the file they came from.
+    pub fn from_source(source: &AnswerSource) -> Result<Self, Box<dyn std::error::Error>> {
+        let json = source.read()?;
+        let word = Word::with_dictionary(&json, read_dictionary()?)
+            .map_err(|err| format!("{}: {}", source.location(), err))?;
+        Ok(word.with_source(source.clone()))
+    }
+    /// Loads lists from files. The dictionary may be JSON or compact.
+    pub fn load(answer_path: &str, dictionary_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let answer = fs::read_to_string(answer_path).map_err(|err| format!("{}: {}", answer_path, err))?;
+        Word::with_dictionary(&answer, Dictionary::from_file(Path::new(dictionary_path))?)
+    }
+    pub fn from_json(answer_json: &str, dictionary_json: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let dictionary = Dictionary::from_json(dictionary_json).map_err(|err| format!("dictionary: {}", err))?;
+        Word::with_dictionary(answer_json, dictionary)
+    }
+    pub fn with_dictionary(answer_json: &str, dictionary: Dictionary) -> Result<Self, Box<dyn std::error::Error>> {
+        let answer: Vec<Vec<String>> = parse_answer(answer_json)
+            .map_err(|err| format!("answers: {}", err))?;
+        Ok(Word {
+            answer,
+            dictionary,
+            pack: None,
+            alphabet: Alphabet::default(),
+            source: None,
+        })
+    }
+    /// Records where the answers were read from, see `source`.
+    pub fn with_source(mut self, source: AnswerSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+    /// Plays `pack` instead: its answers replace the answer lists, and its
+    /// answers and extra guesses join the dictionary. The built-in dictionary
+    /// is only kept for packs in its language.
+    pub fn with_pack(mut self, pack: &Pack) -> Self {
+        if pack.language != pack::DEFAULT_LANGUAGE {
+            self.dictionary.clear();
+        }
+        self.dictionary.extend(pack.answers.iter().flatten().cloned());
+        self.dictionary.extend(pack.guesses.iter().cloned());
+        self.answer = pack.answers.clone();
+        self.pack = Some(pack.id.clone());
+        self.alphabet = pack.alphabet.clone();
+        self
+    }
+    pub fn pack(&self) -> Option<&str> {
+        self.pack.as_deref()
+    }
+    pub fn source(&self) -> Option<&AnswerSource> {
+        self.source.as_ref()
+    }
+    /// The letters words are made of; guesses are folded through it.
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+    /// Word lengths that have at least one answer, ascending.
+    pub fn answer_lengths(&self) -> Vec<usize> {
+        (0..self.answer.len())
+            .filter(|&len| !self.answer[len].is_empty())
+            .collect()
+    }
+    /// Checks the answer list against the dictionary. Returns one message per
+    /// problem found; an empty list means the lists are consistent.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (len, words) in self.answer.iter().enumerate() {
+            let mut seen = HashSet::new();
+            for word in words {
+                if let Err(c) = self.alphabet.fold(word) {
+                    problems.push(format!("'{}' contains '{}', which is not in the alphabet", word, c));
+                }
+                if word.chars().count() != len {
+                    problems.push(format!("'{}' is listed under length {}", word, len));
+                }
+                if !self.dictionary.contains(word) {
+                    problems.push(format!("'{}' is not in the dictionary", word));
+                }
+                if !seen.insert(word) {
+                    problems.push(format!("'{}' is listed more than once", word));
+                }
+            }
+        }
+        problems
     }
     pub fn is_valid_word(&self, word: &str) -> bool {
         self.dictionary.contains(word)
     }
-    pub fn generate_answer(&self, word_length: Option<usize>) -> Result<String, &str> {
+    /// Every word in `source` that meets `query`.
+    pub fn query(&self, query: &WordQuery, source: WordSource) -> Vec<String> {
+        match source {
+            WordSource::Answers => {
+                let lists: Vec<&Vec<String>> = match query.length {
+                    Some(len) => self.answer.get(len).into_iter().collect(),
+                    None => self.answer.iter().collect(),
+                };
+                lists
+                    .into_iter()
+                    .flatten()
+                    .filter(|w| query.matches(w))
+                    .cloned()
+                    .collect()
+            }
+            WordSource::Dictionary => {
+                let mut words: Vec<String> = self
+                    .dictionary
+                    .iter()
+                    .filter(|w| query.matches(w))
+                    .map(String::from)
+                    .collect();
+                words.sort_unstable();
+                words
+            }
+        }
+    }
+    /// Picks an answer using `rng`. Both the length roll (when `word_length`
+    /// is `None`) and the word itself come from `rng`, so a seeded rng always
+    /// produces the same answer. A rolled length without answers, as with
+    /// most packs, is rerolled evenly among the lengths that have some.
+    pub fn generate_answer<R: Rng + ?Sized>(
+        &self,
+        word_length: Option<usize>,
+        rng: &mut R,
+    ) -> Result<String, AnswerError> {
+        let lengths = self.answer_lengths();
         let word_length = match word_length {
             Some(len) => len,
             None => {
-                let mut rng = rand::rng();
                 let x = rng.random::<f32>();
-                if x < 0.2 {
+                let rolled = if x < 0.2 {
                     4
                 } else if x < 0.6 {
                     5
@@ -47,45 +268,26 @@ impl Word {
                     10
                 } else {
                     rng.random_range(11..=15)
+                };
+                match lengths.contains(&rolled) || lengths.is_empty() {
+                    true => rolled,
+                    false => lengths[rng.random_range(0..lengths.len())],
                 }
             }
         };
         if word_length >= self.answer.len() {
-            return Err("Invalid word length.");
+            return Err(AnswerError::InvalidLength(word_length));
         }
         let words = &self.answer[word_length];
         if words.is_empty() {
-            return Err("No words available for the given length.");
+            return Err(AnswerError::NoWords(word_length));
         }
-        let mut rng = rand::rng();
         let index = rng.random_range(0..words.len());
-        return Ok(words[index].clone());
+        Ok(words[index].clone())
     }
 }
-fn load_dictionary(path: &str) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
-    let json_str = fs::read_to_string(path)?;
-    let v: Value = serde_json::from_str(&json_str)?;
-
-    let mut set = HashSet::new();
-
-    if let Value::Object(map) = v {
-        for (_len, words) in map {
-            if let Value::Array(arr) = words {
-                for word in arr {
-                    if let Value::String(s) = word {
-                        set.insert(s);
-                    }
-                }
-            }
-        }
-    }
-
-    Ok(set)
-}
-
-fn load_answer(path: &str) -> Result<Vec<Vec<String>>, Box<dyn std::error::Error>> {
-    let json_str = fs::read_to_string(path)?;
-    let v: serde_json::Value = serde_json::from_str(&json_str)?;
+fn parse_answer(json_str: &str) -> Result<Vec<Vec<String>>, Box<dyn std::error::Error>> {
+    let v: serde_json::Value = serde_json::from_str(json_str)?;
 
     let map = match v {
         serde_json::Value::Object(m) => m,
@@ -113,3 +315,18 @@ fn load_answer(path: &str) -> Result<Vec<Vec<String>>, Box<dyn std::error::Error
 
     Ok(lists)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_list_names_its_file() {
+        let path = std::env::temp_dir().join(format!("wordle-answers-{}.json", std::process::id()));
+        fs::write(&path, "{\"5\": [\"crane\",").unwrap();
+        let source = AnswerSource::File(path.display().to_string());
+        let err = Word::from_source(&source).err().expect("malformed list loaded");
+        fs::remove_file(&path).unwrap();
+        assert!(err.to_string().starts_with(&format!("{}: answers: ", path.display())), "{}", err);
+    }
+}